@@ -0,0 +1,285 @@
+// Seedable random numbers for tests and workloads, shared by several crates:
+// #[path = "../common/prng.rs"] mod prng;
+#![allow(dead_code)]
+
+use std::ops::{Range, RangeInclusive};
+
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    fn next_u32(&mut self) -> u32 {
+        return (self.next_u64() >> 32) as u32;
+    }
+
+    fn next_bool(&mut self) -> bool {
+        return self.next_u64() >> 63 == 1;
+    }
+
+    // Uniform in [0, 1) with all 53 bits of precision
+    fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64);
+    }
+
+    // Uniform in [0, n) without modulo bias (Lemire's method), n must not be zero
+    fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        let mut product = self.next_u64() as u128 * n as u128;
+        if (product as u64) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (product as u64) < threshold {
+                product = self.next_u64() as u128 * n as u128;
+            }
+        }
+        return (product >> 64) as u64;
+    }
+
+    fn range<T: SampleUniform>(&mut self, range: Range<T>) -> T where Self: Sized {
+        return T::sample(self, range.start, range.end, false);
+    }
+
+    fn range_inclusive<T: SampleUniform>(&mut self, range: RangeInclusive<T>) -> T where Self: Sized {
+        let (low, high) = range.into_inner();
+        return T::sample(self, low, high, true);
+    }
+
+    // Fisher–Yates
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    // k distinct items in random order, a partial Fisher–Yates over the indices
+    fn sample<T: Clone>(&mut self, items: &[T], k: usize) -> Vec<T> {
+        assert!(k <= items.len(), "sample larger than the population");
+        let mut indices: Vec<usize> = (0..items.len()).collect();
+        for i in 0..k {
+            let j = i + self.below((items.len() - i) as u64) as usize;
+            indices.swap(i, j);
+        }
+        return indices[..k].iter().map(|i| items[*i].clone()).collect();
+    }
+
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        return Some(&items[self.below(items.len() as u64) as usize]);
+    }
+}
+
+pub trait SampleUniform: Sized {
+    fn sample<R: Rng>(rng: &mut R, low: Self, high: Self, inclusive: bool) -> Self;
+}
+
+macro_rules! impl_sample_int {
+    ($($t:ty => $u:ty),*) => {$(
+        impl SampleUniform for $t {
+            fn sample<R: Rng>(rng: &mut R, low: $t, high: $t, inclusive: bool) -> $t {
+                assert!(if inclusive { low <= high } else { low < high }, "empty range");
+                // the width is taken in the unsigned type, so full ranges do not overflow
+                let width = (high as $u).wrapping_sub(low as $u) as u64;
+                let offset = if !inclusive {
+                    rng.below(width)
+                } else if width == u64::MAX {
+                    rng.next_u64()
+                } else {
+                    rng.below(width + 1)
+                };
+                return (low as $u).wrapping_add(offset as $u) as $t;
+            }
+        }
+    )*};
+}
+
+impl_sample_int!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize,
+                 i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+macro_rules! impl_sample_float {
+    ($($t:ty),*) => {$(
+        impl SampleUniform for $t {
+            fn sample<R: Rng>(rng: &mut R, low: $t, high: $t, inclusive: bool) -> $t {
+                assert!(low.is_finite() && high.is_finite(), "infinite range");
+                assert!(if inclusive { low <= high } else { low < high }, "empty range");
+                let value = low + (high - low) * rng.next_f64() as $t;
+                // rounding can land exactly on the excluded end
+                if !inclusive && value >= high {
+                    return low;
+                }
+                return value.min(high);
+            }
+        }
+    )*};
+}
+
+impl_sample_float!(f32, f64);
+
+// ----------- SPLITMIX64 ------------
+
+// Only used to expand a single seed into a full generator state
+pub struct SplitMix64 {
+    state: u64
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        return SplitMix64 { state: seed };
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        return z ^ (z >> 31);
+    }
+}
+
+// ----------- XOSHIRO256** ------------
+
+pub struct Xoshiro256 {
+    state: [u64; 4]
+}
+
+impl Xoshiro256 {
+    pub fn new(seed: u64) -> Xoshiro256 {
+        let mut seeder = SplitMix64::new(seed);
+        let state = [seeder.next_u64(), seeder.next_u64(), seeder.next_u64(), seeder.next_u64()];
+        return Xoshiro256::from_state(state);
+    }
+
+    // The all zero state is a fixed point and is rejected
+    pub fn from_state(state: [u64; 4]) -> Xoshiro256 {
+        assert!(state != [0; 4], "xoshiro state must not be all zero");
+        return Xoshiro256 { state };
+    }
+}
+
+impl Rng for Xoshiro256 {
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        return result;
+    }
+}
+
+// ----------- PCG32 ------------
+
+// PCG-XSH-RR with 64 bits of state, streams give independent sequences for the same seed
+pub struct Pcg32 {
+    state: u64,
+    increment: u64
+}
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+
+impl Pcg32 {
+    pub fn new(seed: u64, stream: u64) -> Pcg32 {
+        let mut pcg = Pcg32 { state: 0, increment: (stream << 1) | 1 };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(seed);
+        pcg.step();
+        return pcg;
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(PCG_MULTIPLIER).wrapping_add(self.increment);
+    }
+}
+
+impl Rng for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        return xorshifted.rotate_right((old >> 59) as u32);
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        return high << 32 | self.next_u32() as u64;
+    }
+}
+
+#[test]
+fn test_reference_outputs() {
+    let mut xoshiro = Xoshiro256::from_state([1, 2, 3, 4]);
+    let outputs: Vec<u64> = (0..4).map(|_| xoshiro.next_u64()).collect();
+    assert_eq!(outputs, vec![11520, 0, 1509978240, 1215971899390074240]);
+
+    let mut pcg = Pcg32::new(42, 54);
+    let outputs: Vec<u32> = (0..6).map(|_| pcg.next_u32()).collect();
+    assert_eq!(outputs, vec![0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e]);
+
+    let mut splitmix = SplitMix64::new(0);
+    assert_eq!(splitmix.next_u64(), 0xE220A8397B1DCDAF);
+
+    // the same seed reproduces the same sequence
+    let mut a = Xoshiro256::new(7);
+    let mut b = Xoshiro256::new(7);
+    assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+    assert_ne!(Pcg32::new(1, 1).next_u64(), Pcg32::new(1, 2).next_u64());
+}
+
+#[test]
+fn test_ranges() {
+    let mut rng = Xoshiro256::new(1);
+    let mut counts = [0; 6];
+    for _ in 0..60000 {
+        counts[rng.range(0..6usize)] += 1;
+    }
+    assert!(counts.iter().all(|count| (9000..11000).contains(count)), "{:?}", counts);
+
+    for _ in 0..1000 {
+        assert!((-5..5).contains(&rng.range(-5..5i32)));
+        assert!((-5..=5).contains(&rng.range_inclusive(-5..=5i8)));
+        assert!((0.5..1.5).contains(&rng.range(0.5..1.5f64)));
+        assert!((-1.0..=1.0).contains(&rng.range_inclusive(-1.0..=1.0f32)));
+        assert!(rng.next_f64() < 1.0);
+        assert_eq!(rng.range_inclusive(3..=3u16), 3);
+    }
+    assert!((0..1000).any(|_| rng.range_inclusive(i64::MIN..=i64::MAX) < 0));
+    assert!((0..1000).any(|_| rng.range_inclusive(0..=u8::MAX) == u8::MAX));
+    assert!((0..1000).any(|_| rng.range(i8::MIN..i8::MAX) == i8::MIN));
+}
+
+#[test]
+fn test_shuffle_and_sample() {
+    let mut rng = Pcg32::new(2024, 0);
+    let mut items: Vec<u32> = (0..100).collect();
+    rng.shuffle(&mut items);
+    assert_ne!(items, (0..100).collect::<Vec<u32>>());
+    items.sort();
+    assert_eq!(items, (0..100).collect::<Vec<u32>>());
+
+    // every permutation of three items shows up about equally often
+    let mut counts = std::collections::HashMap::new();
+    for _ in 0..60000 {
+        let mut small = [1, 2, 3];
+        rng.shuffle(&mut small);
+        *counts.entry(small).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 6);
+    assert!(counts.values().all(|count| (9000..11000).contains(count)), "{:?}", counts);
+
+    let mut sample = rng.sample(&items, 10);
+    assert_eq!(sample.len(), 10);
+    sample.sort();
+    sample.dedup();
+    assert_eq!(sample.len(), 10);
+    assert_eq!(rng.sample(&items, 0), Vec::<u32>::new());
+    assert_eq!(rng.sample(&[5], 1), vec![5]);
+
+    assert_eq!(rng.choose::<u32>(&[]), None);
+    assert!(items.contains(rng.choose(&items).unwrap()));
+}
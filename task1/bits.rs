@@ -0,0 +1,246 @@
+use crate::int::Int;
+
+pub fn reverse<T: Int>(num: T) -> T {
+    return T::from_bits(num.to_bits().reverse_bits() >> (128 - T::BITS));
+}
+
+// ----------- GRAY CODE ------------
+
+// Binary reflected Gray code, neighbouring values differ in a single bit
+pub fn gray_encode<T: Int>(num: T) -> T {
+    let bits = num.to_bits();
+    return T::from_bits(bits ^ (bits >> 1));
+}
+
+pub fn gray_decode<T: Int>(code: T) -> T {
+    // every bit becomes the xor of itself and all higher bits
+    let mut bits = code.to_bits();
+    let mut shift = 1;
+    while shift < T::BITS {
+        bits ^= bits >> shift;
+        shift *= 2;
+    }
+    return T::from_bits(bits);
+}
+
+// ----------- PARALLEL DEPOSIT / EXTRACT ------------
+
+// Scatters the low bits of num to the positions of the set bits of mask
+pub fn pdep<T: Int>(num: T, mask: T) -> T {
+    let src = num.to_bits();
+    let mut rest = mask.to_bits();
+    let mut result: u128 = 0;
+    let mut i = 0;
+    while rest != 0 {
+        let lowest = rest & rest.wrapping_neg();
+        if (src >> i) & 1 == 1 {
+            result |= lowest;
+        }
+        rest ^= lowest;
+        i += 1;
+    }
+    return T::from_bits(result);
+}
+
+// Gathers the bits of num at the set bits of mask into the low bits
+pub fn pext<T: Int>(num: T, mask: T) -> T {
+    let src = num.to_bits();
+    let mut rest = mask.to_bits();
+    let mut result: u128 = 0;
+    let mut i = 0;
+    while rest != 0 {
+        let lowest = rest & rest.wrapping_neg();
+        if src & lowest != 0 {
+            result |= 1 << i;
+        }
+        rest ^= lowest;
+        i += 1;
+    }
+    return T::from_bits(result);
+}
+
+// ----------- PERMUTATIONS ------------
+
+// Bit i of the result is bit table[i] of num, the table may repeat or skip
+// source bits and leaves the result bits past its end cleared.
+pub fn permute_bits<T: Int>(num: T, table: &[u32]) -> T {
+    assert!(table.len() <= T::BITS as usize, "permutation table is wider than the type");
+    let src = num.to_bits();
+    let mut result: u128 = 0;
+    for (i, from) in table.iter().enumerate() {
+        assert!(*from < T::BITS, "permutation index {} out of range", from);
+        result |= ((src >> from) & 1) << i;
+    }
+    return T::from_bits(result);
+}
+
+// Bit c of byte r holds element (r, c) of an 8x8 bit matrix
+pub fn transpose8(matrix: u64) -> u64 {
+    let mut x = matrix;
+    let mut t = (x ^ (x >> 7)) & 0x00AA00AA00AA00AA;
+    x = x ^ t ^ (t << 7);
+    t = (x ^ (x >> 14)) & 0x0000CCCC0000CCCC;
+    x = x ^ t ^ (t << 14);
+    t = (x ^ (x >> 28)) & 0x00000000F0F0F0F0;
+    x = x ^ t ^ (t << 28);
+    return x;
+}
+
+// Bit c of rows[r] holds element (r, c) of a 64x64 bit matrix,
+// the blocks are swapped recursively from 32x32 down to 1x1.
+pub fn transpose64(rows: &mut [u64; 64]) {
+    let mut width = 32;
+    let mut mask: u64 = 0x00000000FFFFFFFF;
+    while width != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let t = ((rows[k] >> width) ^ rows[k + width]) & mask;
+            rows[k] ^= t << width;
+            rows[k + width] ^= t;
+            k = (k + width + 1) & !width;
+        }
+        width >>= 1;
+        mask ^= mask << width;
+    }
+}
+
+fn sample_bits(count: usize) -> Vec<u128> {
+    let mut state: u64 = 0x853C49E6748FEA9B;
+    let mut values = vec![0, 1, u128::MAX, 1 << 127, 0x5555 << 64];
+    for _ in 0..count {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let high = state;
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        values.push((high as u128) << 64 | state as u128);
+    }
+    return values;
+}
+
+fn check_naive<T: Int + std::fmt::Debug>(num: T, mask: T) {
+    let (bits, mask_bits) = (num.to_bits(), mask.to_bits());
+
+    let mut reversed: u128 = 0;
+    let mut gray: u128 = 0;
+    let mut deposited: u128 = 0;
+    let mut extracted: u128 = 0;
+    let mut used = 0;
+    for i in 0..T::BITS {
+        reversed |= ((bits >> i) & 1) << (T::BITS - i - 1);
+        let next = if i + 1 < T::BITS { (bits >> (i + 1)) & 1 } else { 0 };
+        gray |= (((bits >> i) & 1) ^ next) << i;
+        if (mask_bits >> i) & 1 == 1 {
+            deposited |= ((bits >> used) & 1) << i;
+            extracted |= ((bits >> i) & 1) << used;
+            used += 1;
+        }
+    }
+
+    assert_eq!(reverse(num), T::from_bits(reversed));
+    assert_eq!(gray_encode(num), T::from_bits(gray));
+    assert_eq!(gray_decode(gray_encode(num)), num);
+    assert_eq!(pdep(num, mask), T::from_bits(deposited));
+    assert_eq!(pext(num, mask), T::from_bits(extracted));
+    let low = if used == 128 { u128::MAX } else { (1 << used) - 1 };
+    assert_eq!(pext(pdep(num, mask), mask), T::from_bits(bits & low));
+}
+
+#[test]
+fn test_gray_code() {
+    assert_eq!(gray_encode(0u8), 0);
+    assert_eq!(gray_encode(1u8), 1);
+    assert_eq!(gray_encode(2u8), 3);
+    assert_eq!(gray_encode(3u8), 2);
+    assert_eq!(gray_encode(-1i8), i8::MIN);
+    assert_eq!(gray_decode(i8::MIN), -1);
+
+    for num in 0..=u16::MAX {
+        let code = gray_encode(num);
+        assert_eq!((code ^ gray_encode(num.wrapping_add(1))).count_ones(), 1);
+        assert_eq!(gray_decode(code), num);
+    }
+}
+
+#[test]
+fn test_against_naive() {
+    let samples = sample_bits(500);
+    for pair in samples.windows(2) {
+        let (num, mask) = (pair[0], pair[1]);
+        check_naive(num as u8, mask as u8);
+        check_naive(num as i16, mask as i16);
+        check_naive(num as u32, mask as u32);
+        check_naive(num as i64, mask as i64);
+        check_naive(num as usize, mask as usize);
+        check_naive(num, mask);
+        check_naive(num as i128, mask as i128);
+    }
+    for num in 0..=u8::MAX {
+        for mask in 0..=u8::MAX {
+            check_naive(num, mask);
+        }
+    }
+
+    assert_eq!(pdep(0b1011u32, 0b1111_0000), 0b1011_0000);
+    assert_eq!(pdep(0b101u32, 0b1010_1000), 0b1000_1000);
+    assert_eq!(pext(0b1011_0000u32, 0b1111_0000), 0b1011);
+    assert_eq!(pext(-1i8, 0b0101_0101), 0b1111);
+}
+
+#[test]
+fn test_permutations() {
+    let identity: Vec<u32> = (0..16).collect();
+    let reversed: Vec<u32> = (0..16).rev().collect();
+    let rotated: Vec<u32> = (0..32).map(|i| (i + 3) % 32).collect();
+    let swapped: Vec<u32> = (0..8).map(|i| i ^ 1).collect();
+
+    for num in sample_bits(200) {
+        assert_eq!(permute_bits(num as u16, &identity), num as u16);
+        assert_eq!(permute_bits(num as u16, &reversed), reverse(num as u16));
+        assert_eq!(permute_bits(num as u32, &rotated), (num as u32).rotate_right(3));
+        assert_eq!(permute_bits(num as i8, &swapped),
+                   ((num as u8 & 0x55) << 1 | (num as u8 & 0xAA) >> 1) as i8);
+    }
+    assert_eq!(permute_bits(0xFFu8, &[0, 0]), 0b11);
+    assert_eq!(permute_bits(0b10u8, &[1, 1, 1]), 0b111);
+}
+
+#[test]
+#[should_panic]
+fn test_permutation_out_of_range() {
+    permute_bits(1u8, &[8]);
+}
+
+#[test]
+fn test_transpose() {
+    for sample in sample_bits(200) {
+        let matrix = sample as u64;
+        let transposed = transpose8(matrix);
+        for r in 0..8 {
+            for c in 0..8 {
+                assert_eq!((transposed >> (8 * r + c)) & 1, (matrix >> (8 * c + r)) & 1);
+            }
+        }
+        assert_eq!(transpose8(transposed), matrix);
+    }
+
+    let samples = sample_bits(64);
+    let mut rows = [0u64; 64];
+    for i in 0..64 {
+        rows[i] = samples[i] as u64;
+    }
+    let original = rows;
+    transpose64(&mut rows);
+    for r in 0..64 {
+        for c in 0..64 {
+            assert_eq!((rows[r] >> c) & 1, (original[c] >> r) & 1);
+        }
+    }
+    transpose64(&mut rows);
+    assert_eq!(rows, original);
+
+    let mut diagonal = [0u64; 64];
+    for i in 0..64 {
+        diagonal[i] = 1 << i;
+    }
+    transpose64(&mut diagonal);
+    assert_eq!(diagonal.iter().enumerate().all(|(i, row)| *row == 1 << i), true);
+}
@@ -0,0 +1,224 @@
+// Fixed width versions of the task helpers. Trait methods can not be called
+// in const fn, so instead of the Int trait every width gets its own function.
+
+macro_rules! const_bit_fns {
+    ($($t:ty => $reverse:ident, $power:ident);*) => {$(
+        pub const fn $reverse(num: $t) -> $t {
+            let mut reversed: $t = 0;
+            let mut i = 0;
+            while i < <$t>::BITS {
+                let bit = (num >> i) & 1;
+                reversed = reversed | (bit << (<$t>::BITS - i - 1));
+                i += 1;
+            }
+            return reversed;
+        }
+
+        pub const fn $power(num: $t) -> bool {
+            return num.count_ones() == 1;
+        }
+    )*};
+}
+
+macro_rules! const_power_fns {
+    ($($t:ty => $power:ident);*) => {$(
+        pub const fn $power(num: $t) -> bool {
+            return num > 0 && num.count_ones() == 1;
+        }
+    )*};
+}
+
+// Negative numbers have no digits to sum and are never palindromes,
+// the same as for the i32 helpers.
+macro_rules! const_digit_fns {
+    ($($t:ty => $digit_sum:ident, $add_digits:ident, $palindrome:ident);*) => {$(
+        pub const fn $digit_sum(num: $t) -> $t {
+            let mut total = 0;
+            let mut divided = num;
+            while divided > 0 {
+                total += divided % 10;
+                divided = divided / 10;
+            }
+            return total;
+        }
+
+        pub const fn $add_digits(num: $t) -> $t {
+            let mut result = num;
+            while result > 9 {
+                result = $digit_sum(result);
+            }
+            return result;
+        }
+
+        // compares the outer digits pairwise, so the reversed number
+        // never has to fit into the type
+        pub const fn $palindrome(num: $t) -> bool {
+            if num < 0 as $t {
+                return false;
+            }
+
+            let mut high: $t = 1;
+            while high <= num / 10 {
+                high *= 10;
+            }
+            let mut rest = num;
+            while high > 1 {
+                if rest / high != rest % 10 {
+                    return false;
+                }
+                rest = rest % high / 10;
+                high /= 100;
+            }
+            return true;
+        }
+    )*};
+}
+
+const_bit_fns!(
+    u8 => reverse_bits_u8, is_power_of_two_u8;
+    u16 => reverse_bits_u16, is_power_of_two_u16;
+    u32 => reverse_bits_u32, is_power_of_two_u32;
+    u64 => reverse_bits_u64, is_power_of_two_u64;
+    u128 => reverse_bits_u128, is_power_of_two_u128;
+    usize => reverse_bits_usize, is_power_of_two_usize
+);
+
+const_power_fns!(
+    i8 => is_power_of_two_i8;
+    i16 => is_power_of_two_i16;
+    i32 => is_power_of_two_i32;
+    i64 => is_power_of_two_i64;
+    i128 => is_power_of_two_i128;
+    isize => is_power_of_two_isize
+);
+
+const_digit_fns!(
+    u8 => digit_sum_u8, add_digits_u8, find_palindrome_u8;
+    u16 => digit_sum_u16, add_digits_u16, find_palindrome_u16;
+    u32 => digit_sum_u32, add_digits_u32, find_palindrome_u32;
+    u64 => digit_sum_u64, add_digits_u64, find_palindrome_u64;
+    u128 => digit_sum_u128, add_digits_u128, find_palindrome_u128;
+    usize => digit_sum_usize, add_digits_usize, find_palindrome_usize;
+    i8 => digit_sum_i8, add_digits_i8, find_palindrome_i8;
+    i16 => digit_sum_i16, add_digits_i16, find_palindrome_i16;
+    i32 => digit_sum_i32, add_digits_i32, find_palindrome_i32;
+    i64 => digit_sum_i64, add_digits_i64, find_palindrome_i64;
+    i128 => digit_sum_i128, add_digits_i128, find_palindrome_i128;
+    isize => digit_sum_isize, add_digits_isize, find_palindrome_isize
+);
+
+// ----------- TABLES ------------
+
+const fn rev8_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = reverse_bits_u8(i as u8);
+        i += 1;
+    }
+    return table;
+}
+
+const fn pow10_table() -> [u64; 20] {
+    let mut table = [1u64; 20];
+    let mut i = 1;
+    while i < 20 {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    return table;
+}
+
+pub const REV8: [u8; 256] = rev8_table();
+pub const POW10: [u64; 20] = pow10_table();
+
+// reverses a word byte by byte through the table
+pub const fn reverse_bits_table(num: u32) -> u32 {
+    let bytes = num.to_le_bytes();
+    return u32::from_be_bytes([REV8[bytes[0] as usize], REV8[bytes[1] as usize],
+                               REV8[bytes[2] as usize], REV8[bytes[3] as usize]]);
+}
+
+#[test]
+fn test_const_tables() {
+    use crate::{reverse_bits, digit_sum};
+    const DIGITS: usize = digit_sum(99_999) as usize;
+    const MIRRORED: u32 = reverse_bits(reverse_bits_table(0xF00D));
+    const DOUBLE_REVERSED: [u8; 256] = {
+        let mut table = REV8;
+        let mut i = 0;
+        while i < 256 {
+            table[i] = REV8[table[i] as usize];
+            i += 1;
+        }
+        table
+    };
+
+    for i in 0..256 {
+        assert_eq!(REV8[i], (i as u8).reverse_bits());
+        assert_eq!(DOUBLE_REVERSED[i], i as u8);
+    }
+    for i in 0..20 {
+        assert_eq!(POW10[i], 10u64.pow(i as u32));
+    }
+
+    let buffer = [0u8; DIGITS];
+    assert_eq!(buffer.len(), 45);
+    assert_eq!(MIRRORED, 0xF00D);
+}
+
+#[test]
+fn test_const_widths() {
+    use crate::{reverse_bits, digit_sum, find_palindrome};
+    const REVERSED: (u8, u16, u64, u128) = (
+        reverse_bits_u8(1), reverse_bits_u16(0x00F0), reverse_bits_u64(1 << 63), reverse_bits_u128(3)
+    );
+    const POWERS: [bool; 6] = [
+        is_power_of_two_u8(128), is_power_of_two_i8(i8::MIN), is_power_of_two_u32(0),
+        is_power_of_two_i64(1 << 40), is_power_of_two_u128(1 << 127), is_power_of_two_isize(-4)
+    ];
+    const SUMS: (u8, i16, u64, i128) = (
+        add_digits_u8(255), digit_sum_i16(-123), digit_sum_u64(u64::MAX), add_digits_i128(i128::MAX)
+    );
+    const PALINDROMES: [bool; 6] = [
+        find_palindrome_u8(252), find_palindrome_u16(1001), find_palindrome_u64(18446744066044764481),
+        find_palindrome_i64(-121), find_palindrome_u128(1021), find_palindrome_i8(0)
+    ];
+
+    assert_eq!(REVERSED, (128, 0x0F00, 1, 3 << 126));
+    assert_eq!(POWERS, [true, false, false, true, true, false]);
+    assert_eq!(SUMS, (3, 0, 87, 1));
+    assert_eq!(PALINDROMES, [true, true, true, false, false, true]);
+    assert_eq!(reverse_bits_usize(1), 1 << (usize::BITS - 1));
+    assert_eq!(is_power_of_two_u16(3), false);
+    assert_eq!(is_power_of_two_i16(16), true);
+    assert_eq!(is_power_of_two_i128(-1), false);
+    assert_eq!(is_power_of_two_usize(64), true);
+    assert_eq!(is_power_of_two_u64(u64::MAX), false);
+    assert_eq!((add_digits_u16(9999), add_digits_u32(123456789), add_digits_u64(0)), (9, 9, 0));
+    assert_eq!((add_digits_i8(99), add_digits_i16(4444), add_digits_i64(555)), (9, 7, 6));
+    assert_eq!((digit_sum_u8(99), digit_sum_u16(999), digit_sum_u32(1234)), (18, 27, 10));
+    assert_eq!((digit_sum_u128(u128::MAX), digit_sum_i8(-1), digit_sum_i64(101)), (165, 0, 2));
+    assert_eq!((digit_sum_i128(i128::MIN), add_digits_u128(10)), (0, 1));
+    assert_eq!((find_palindrome_u32(123321), find_palindrome_i16(i16::MAX)), (true, false));
+    assert_eq!((find_palindrome_i128(i128::MAX), find_palindrome_u8(10)), (false, false));
+
+    assert_eq!((reverse_bits_u32(1), is_power_of_two_i32(1 << 30), is_power_of_two_i32(i32::MIN)),
+               (1 << 31, true, false));
+    assert_eq!((digit_sum_usize(1234), add_digits_usize(99), find_palindrome_usize(1221)), (10, 9, true));
+    assert_eq!((digit_sum_isize(-12), add_digits_isize(99), find_palindrome_isize(12321)), (0, 9, true));
+
+    // the overflow free palindrome check agrees with the reversing one, the
+    // i32 variants with the task helpers
+    for num in 0..200_000u32 {
+        assert_eq!(find_palindrome_u32(num), find_palindrome(num as i32));
+        assert_eq!(find_palindrome_i32(num as i32), find_palindrome(num as i32));
+        assert_eq!(digit_sum_i32(num as i32), digit_sum(num as i32));
+        assert_eq!(add_digits_i32(num as i32), crate::add_digits(num as i32));
+        assert_eq!(reverse_bits_u32(num), reverse_bits(num));
+    }
+    for num in 0..=u16::MAX {
+        assert_eq!(reverse_bits_u16(num), num.reverse_bits());
+        assert_eq!(is_power_of_two_u16(num), num.is_power_of_two());
+    }
+}
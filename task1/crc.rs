@@ -0,0 +1,329 @@
+use std::fmt::{Display, Formatter};
+use crate::reverse_bits;
+
+// Parameters in the usual Rocksoft model notation, check is the CRC of "123456789"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrcParams {
+    pub width: u32,
+    pub poly: u64,
+    pub init: u64,
+    pub refin: bool,
+    pub refout: bool,
+    pub xorout: u64,
+    pub check: u64
+}
+
+pub const CRC_8: CrcParams = CrcParams {
+    width: 8, poly: 0x07, init: 0x00, refin: false, refout: false, xorout: 0x00, check: 0xF4
+};
+
+// CRC-16/CCITT-FALSE, also known as CRC-16/IBM-3740
+pub const CRC_16_CCITT: CrcParams = CrcParams {
+    width: 16, poly: 0x1021, init: 0xFFFF, refin: false, refout: false, xorout: 0x0000, check: 0x29B1
+};
+
+pub const CRC_16_KERMIT: CrcParams = CrcParams {
+    width: 16, poly: 0x1021, init: 0x0000, refin: true, refout: true, xorout: 0x0000, check: 0x2189
+};
+
+pub const CRC_32: CrcParams = CrcParams {
+    width: 32, poly: 0x04C11DB7, init: 0xFFFFFFFF, refin: true, refout: true,
+    xorout: 0xFFFFFFFF, check: 0xCBF43926
+};
+
+pub const CRC_32C: CrcParams = CrcParams {
+    width: 32, poly: 0x1EDC6F41, init: 0xFFFFFFFF, refin: true, refout: true,
+    xorout: 0xFFFFFFFF, check: 0xE3069283
+};
+
+pub const CRC_64_XZ: CrcParams = CrcParams {
+    width: 64, poly: 0x42F0E1EBA9EA3693, init: u64::MAX, refin: true, refout: true,
+    xorout: u64::MAX, check: 0x995DC9BBDF1939FA
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrcError {
+    // the register is a u64, widths from 1 to 64 bits are supported
+    InvalidWidth(u32)
+}
+
+impl Display for CrcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            CrcError::InvalidWidth(width) => write!(f, "crc width {} is not between 1 and 64", width)
+        };
+    }
+}
+
+impl std::error::Error for CrcError {}
+
+fn check_width(params: &CrcParams) -> Result<(), CrcError> {
+    if params.width == 0 || params.width > 64 {
+        return Err(CrcError::InvalidWidth(params.width));
+    }
+    return Ok(());
+}
+
+fn width_mask(width: u32) -> u64 {
+    return u64::MAX >> (64 - width);
+}
+
+// Reverses the low width bits of value
+fn reflect(value: u64, width: u32) -> u64 {
+    let reversed = (reverse_bits(value as u32) as u64) << 32 | reverse_bits((value >> 32) as u32) as u64;
+    return reversed >> (64 - width);
+}
+
+// Reference implementation, one bit at a time straight from the definition.
+// Panics on an invalid width like Crc::new.
+pub fn checksum_bitwise(params: &CrcParams, data: &[u8]) -> u64 {
+    if let Err(error) = check_width(params) {
+        panic!("{}", error);
+    }
+    let mask = width_mask(params.width);
+    let mut reg = params.init & mask;
+    for byte in data {
+        let byte = if params.refin { reflect(*byte as u64, 8) } else { *byte as u64 };
+        for i in (0..8).rev() {
+            let top = (reg >> (params.width - 1)) & 1;
+            reg = (reg << 1) & mask;
+            if top ^ ((byte >> i) & 1) == 1 {
+                reg ^= params.poly;
+            }
+        }
+    }
+    if params.refout {
+        reg = reflect(reg, params.width);
+    }
+    return (reg ^ params.xorout) & mask;
+}
+
+// Reflected CRCs keep the reflected register in the low bits, the others keep
+// the register in the high bits, so both shift whole bytes out of a u64.
+pub struct Crc {
+    params: CrcParams,
+    // tables[k][b] is the register contribution of byte b followed by k zero bytes
+    tables: Box<[[u64; 256]; 8]>
+}
+
+impl Crc {
+    // Panics on an invalid width, checked_new returns the error
+    pub fn new(params: CrcParams) -> Crc {
+        return match Crc::checked_new(params) {
+            Ok(crc) => crc,
+            Err(error) => panic!("{}", error)
+        };
+    }
+
+    pub fn checked_new(params: CrcParams) -> Result<Crc, CrcError> {
+        check_width(&params)?;
+        let mut tables = Box::new([[0u64; 256]; 8]);
+        let reflected_poly = reflect(params.poly, params.width);
+        let aligned_poly = params.poly << (64 - params.width);
+
+        for byte in 0..256 {
+            let mut reg: u64;
+            if params.refin {
+                reg = byte as u64;
+                for _ in 0..8 {
+                    reg = if reg & 1 == 1 { (reg >> 1) ^ reflected_poly } else { reg >> 1 };
+                }
+            } else {
+                reg = (byte as u64) << 56;
+                for _ in 0..8 {
+                    reg = if reg >> 63 == 1 { (reg << 1) ^ aligned_poly } else { reg << 1 };
+                }
+            }
+            tables[0][byte] = reg;
+        }
+
+        for k in 1..8 {
+            for byte in 0..256 {
+                let prev = tables[k - 1][byte];
+                tables[k][byte] = if params.refin {
+                    (prev >> 8) ^ tables[0][(prev & 0xff) as usize]
+                } else {
+                    (prev << 8) ^ tables[0][(prev >> 56) as usize]
+                };
+            }
+        }
+        return Ok(Crc { params, tables });
+    }
+
+    pub fn params(&self) -> &CrcParams {
+        return &self.params;
+    }
+
+    pub fn digest(&self) -> Digest<'_> {
+        let init = self.params.init & width_mask(self.params.width);
+        let state = if self.params.refin {
+            reflect(init, self.params.width)
+        } else {
+            init << (64 - self.params.width)
+        };
+        return Digest { crc: self, state };
+    }
+
+    // Slice-by-8, eight bytes per step
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let mut digest = self.digest();
+        digest.update(data);
+        return digest.finalize();
+    }
+
+    // Classic table-driven version, one byte per step
+    pub fn checksum_bytewise(&self, data: &[u8]) -> u64 {
+        let mut digest = self.digest();
+        for byte in data {
+            digest.update_byte(*byte);
+        }
+        return digest.finalize();
+    }
+}
+
+pub struct Digest<'a> {
+    crc: &'a Crc,
+    state: u64
+}
+
+impl<'a> Digest<'a> {
+    fn update_byte(&mut self, byte: u8) {
+        let table = &self.crc.tables[0];
+        if self.crc.params.refin {
+            self.state = (self.state >> 8) ^ table[((self.state ^ byte as u64) & 0xff) as usize];
+        } else {
+            self.state = (self.state << 8) ^ table[((self.state >> 56) ^ byte as u64) as usize];
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let tables = &self.crc.tables;
+        let mut chunks = data.chunks_exact(8);
+        for chunk in &mut chunks {
+            let chunk: [u8; 8] = chunk.try_into().unwrap();
+            let mut next = 0;
+            if self.crc.params.refin {
+                let x = self.state ^ u64::from_le_bytes(chunk);
+                for k in 0..8 {
+                    next ^= tables[7 - k][((x >> (8 * k)) & 0xff) as usize];
+                }
+            } else {
+                let x = self.state ^ u64::from_be_bytes(chunk);
+                for k in 0..8 {
+                    next ^= tables[k][((x >> (8 * k)) & 0xff) as usize];
+                }
+            }
+            self.state = next;
+        }
+        for byte in chunks.remainder() {
+            self.update_byte(*byte);
+        }
+    }
+
+    pub fn finalize(&self) -> u64 {
+        let params = &self.crc.params;
+        let mut value;
+        if params.refin {
+            value = self.state;
+            if !params.refout {
+                value = reflect(value, params.width);
+            }
+        } else {
+            value = self.state >> (64 - params.width);
+            if params.refout {
+                value = reflect(value, params.width);
+            }
+        }
+        return (value ^ params.xorout) & width_mask(params.width);
+    }
+}
+
+const CRC_3_GSM: CrcParams = CrcParams {
+    width: 3, poly: 0x3, init: 0x0, refin: false, refout: false, xorout: 0x7, check: 0x4
+};
+
+const CRC_5_USB: CrcParams = CrcParams {
+    width: 5, poly: 0x05, init: 0x1F, refin: true, refout: true, xorout: 0x1F, check: 0x19
+};
+
+const CRC_12_UMTS: CrcParams = CrcParams {
+    width: 12, poly: 0x80F, init: 0x000, refin: false, refout: true, xorout: 0x000, check: 0xDAF
+};
+
+const CRC_64_ECMA: CrcParams = CrcParams {
+    width: 64, poly: 0x42F0E1EBA9EA3693, init: 0, refin: false, refout: false,
+    xorout: 0, check: 0x6C40DF5F0B497347
+};
+
+const ALL_PARAMS: [CrcParams; 10] = [
+    CRC_8, CRC_16_CCITT, CRC_16_KERMIT, CRC_32, CRC_32C, CRC_64_XZ,
+    CRC_3_GSM, CRC_5_USB, CRC_12_UMTS, CRC_64_ECMA
+];
+
+#[test]
+fn test_check_values() {
+    let data = b"123456789";
+    for params in ALL_PARAMS {
+        let crc = Crc::new(params);
+        assert_eq!(crc.params(), &params);
+        assert_eq!(checksum_bitwise(&params, data), params.check, "{:?}", params);
+        assert_eq!(crc.checksum_bytewise(data), params.check, "{:?}", params);
+        assert_eq!(crc.checksum(data), params.check, "{:?}", params);
+    }
+
+    assert_eq!(Crc::new(CRC_32).checksum(b""), 0);
+    assert_eq!(Crc::new(CRC_32).checksum(b"The quick brown fox jumps over the lazy dog"), 0x414FA339);
+}
+
+#[test]
+fn test_invalid_width() {
+    for width in [0, 65, u32::MAX] {
+        let params = CrcParams { width, ..CRC_8 };
+        assert_eq!(Crc::checked_new(params).err(), Some(CrcError::InvalidWidth(width)));
+    }
+    assert!(Crc::checked_new(CrcParams { width: 1, poly: 1, ..CRC_8 }).is_ok());
+    assert_eq!(CrcError::InvalidWidth(0).to_string(), "crc width 0 is not between 1 and 64");
+}
+
+#[test]
+#[should_panic(expected = "crc width 65 is not between 1 and 64")]
+fn test_invalid_width_panic() {
+    let _ = Crc::new(CrcParams { width: 65, ..CRC_32 });
+}
+
+#[test]
+#[should_panic(expected = "crc width 0 is not between 1 and 64")]
+fn test_invalid_width_bitwise() {
+    let _ = checksum_bitwise(&CrcParams { width: 0, ..CRC_32 }, b"1");
+}
+
+#[test]
+fn test_implementations_agree() {
+    let mut data = vec![];
+    let mut state: u32 = 0x12345678;
+    for _ in 0..1000 {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        data.push(state as u8);
+    }
+
+    for params in ALL_PARAMS {
+        let crc = Crc::new(params);
+        for len in [0, 1, 7, 8, 9, 15, 16, 17, 63, 64, 100, 1000] {
+            let expected = checksum_bitwise(&params, &data[..len]);
+            assert_eq!(crc.checksum_bytewise(&data[..len]), expected);
+            assert_eq!(crc.checksum(&data[..len]), expected);
+        }
+
+        // streaming in uneven pieces gives the same result as one call
+        let mut digest = crc.digest();
+        let mut start = 0;
+        for piece in [3, 0, 8, 13, 1, 64, 211] {
+            digest.update(&data[start..start + piece]);
+            start += piece;
+        }
+        digest.update(&data[start..]);
+        assert_eq!(digest.finalize(), checksum_bitwise(&params, &data));
+    }
+}
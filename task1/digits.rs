@@ -0,0 +1,598 @@
+use std::fmt::{Display, Formatter};
+use crate::int::Int;
+
+// ----------- DIGIT ITERATOR ------------
+
+// Digits of the magnitude of a number, most significant first.
+// Zero has a single digit, the base may be any number from 2 up.
+pub struct Digits {
+    rest: u128,
+    high: u128,
+    base: u128,
+    len: usize
+}
+
+pub fn digits<T: Int>(num: T, base: u32) -> Digits {
+    assert!(base >= 2, "base must be at least 2");
+    let rest = num.magnitude();
+    let base = base as u128;
+    let mut high = 1;
+    let mut len = 1;
+    while high <= rest / base {
+        high *= base;
+        len += 1;
+    }
+    return Digits { rest, high, base, len };
+}
+
+impl Iterator for Digits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.rest / self.high;
+        self.rest %= self.high;
+        self.high /= self.base;
+        self.len -= 1;
+        return Some(digit as u32);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.len, Some(self.len));
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.rest % self.base;
+        self.rest /= self.base;
+        self.high /= self.base;
+        self.len -= 1;
+        return Some(digit as u32);
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+// ----------- ERRORS ------------
+
+// Positions are byte offsets into the parsed string
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseNumberError {
+    Empty,
+    InvalidCharacter(usize),
+    InvalidWord(usize),
+    InvalidGrouping(usize),
+    NonCanonical,
+    NotAnInteger,
+    OutOfRange
+}
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ParseNumberError::Empty => write!(f, "empty string"),
+            ParseNumberError::InvalidCharacter(pos) => write!(f, "invalid character at {}", pos),
+            ParseNumberError::InvalidWord(pos) => write!(f, "invalid word at {}", pos),
+            ParseNumberError::InvalidGrouping(pos) => write!(f, "invalid digit grouping at {}", pos),
+            ParseNumberError::NonCanonical => write!(f, "number is not written in canonical form"),
+            ParseNumberError::NotAnInteger => write!(f, "number is not an integer"),
+            ParseNumberError::OutOfRange => write!(f, "number does not fit the target type")
+        };
+    }
+}
+
+impl std::error::Error for ParseNumberError {}
+
+fn from_parts<T: Int>(magnitude: u128, negative: bool) -> Result<T, ParseNumberError> {
+    let limit = if !T::SIGNED {
+        if negative && magnitude != 0 {
+            return Err(ParseNumberError::OutOfRange);
+        }
+        u128::MAX >> (128 - T::BITS)
+    } else if negative {
+        1 << (T::BITS - 1)
+    } else {
+        (1 << (T::BITS - 1)) - 1
+    };
+    if magnitude > limit {
+        return Err(ParseNumberError::OutOfRange);
+    }
+    return Ok(T::from_magnitude(magnitude, negative));
+}
+
+// ----------- ROMAN NUMERALS ------------
+
+const ROMAN_SYMBOLS: [[char; 3]; 4] = [['I', 'V', 'X'], ['X', 'L', 'C'], ['C', 'D', 'M'], ['M', ' ', ' ']];
+
+// Only 1..=3999 can be written without overlines
+pub fn to_roman(num: u32) -> Option<String> {
+    if num == 0 || num > 3999 {
+        return None;
+    }
+
+    let mut roman = String::new();
+    let mut place = digits(num, 10).len();
+    for digit in digits(num, 10) {
+        place -= 1;
+        let [one, five, ten] = ROMAN_SYMBOLS[place];
+        match digit {
+            0..=3 => (0..digit).for_each(|_| roman.push(one)),
+            4 => { roman.push(one); roman.push(five); }
+            5..=8 => { roman.push(five); (5..digit).for_each(|_| roman.push(one)); }
+            _ => { roman.push(one); roman.push(ten); }
+        }
+    }
+    return Some(roman);
+}
+
+// Accepts only the canonical form produced by to_roman, so "IIII" or "IM" are rejected
+pub fn from_roman(text: &str) -> Result<u32, ParseNumberError> {
+    if text.is_empty() {
+        return Err(ParseNumberError::Empty);
+    }
+
+    let mut total: u32 = 0;
+    let mut prev: u32 = 0;
+    for (pos, c) in text.char_indices().rev() {
+        let value = match c {
+            'I' => 1, 'V' => 5, 'X' => 10, 'L' => 50, 'C' => 100, 'D' => 500, 'M' => 1000,
+            _ => return Err(ParseNumberError::InvalidCharacter(pos))
+        };
+        if value < prev {
+            total = total.checked_sub(value).ok_or(ParseNumberError::NonCanonical)?;
+        } else {
+            total = total.checked_add(value).ok_or(ParseNumberError::OutOfRange)?;
+            prev = value;
+        }
+    }
+
+    if to_roman(total).as_deref() != Some(text) {
+        return Err(ParseNumberError::NonCanonical);
+    }
+    return Ok(total);
+}
+
+// ----------- ENGLISH WORDS ------------
+
+const SMALL: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen"
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"
+];
+
+const SCALES: [&str; 13] = [
+    "", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion",
+    "sextillion", "septillion", "octillion", "nonillion", "decillion", "undecillion"
+];
+
+fn group_to_words(group: u32, words: &mut Vec<String>) {
+    if group >= 100 {
+        words.push(SMALL[(group / 100) as usize].to_string());
+        words.push(String::from("hundred"));
+    }
+    let rest = group % 100;
+    if rest >= 20 {
+        if rest % 10 == 0 {
+            words.push(TENS[(rest / 10) as usize].to_string());
+        } else {
+            words.push(format!("{}-{}", TENS[(rest / 10) as usize], SMALL[(rest % 10) as usize]));
+        }
+    } else if rest > 0 {
+        words.push(SMALL[rest as usize].to_string());
+    }
+}
+
+// Short scale American English without "and": 1105 is "one thousand one hundred five"
+pub fn to_words<T: Int>(num: T) -> String {
+    if num.magnitude() == 0 {
+        return String::from("zero");
+    }
+
+    let mut words: Vec<String> = vec![];
+    if num.is_negative() {
+        words.push(String::from("negative"));
+    }
+    let mut scale = digits(num, 1000).len();
+    for group in digits(num, 1000) {
+        scale -= 1;
+        if group == 0 {
+            continue;
+        }
+        group_to_words(group, &mut words);
+        if scale > 0 {
+            words.push(SCALES[scale].to_string());
+        }
+    }
+    return words.join(" ");
+}
+
+// Case and spacing are free, otherwise only the form produced by to_words is accepted
+pub fn from_words<T: Int>(text: &str) -> Result<T, ParseNumberError> {
+    let lowered = text.to_lowercase();
+    if lowered.trim().is_empty() {
+        return Err(ParseNumberError::Empty);
+    }
+
+    let mut negative = false;
+    let mut total: u128 = 0;
+    let mut group: u128 = 0;
+    let mut first = true;
+    let tokens = lowered.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|token| (token.as_ptr() as usize - lowered.as_ptr() as usize, token))
+        .filter(|(_, token)| !token.is_empty());
+    for (pos, token) in tokens {
+        if first && token == "negative" {
+            negative = true;
+            first = false;
+            continue;
+        }
+        first = false;
+
+        if let Some(value) = SMALL.iter().position(|word| *word == token) {
+            group = group.checked_add(value as u128).ok_or(ParseNumberError::OutOfRange)?;
+        } else if let Some(value) = TENS.iter().position(|word| !word.is_empty() && *word == token) {
+            group = group.checked_add(value as u128 * 10).ok_or(ParseNumberError::OutOfRange)?;
+        } else if token == "hundred" {
+            group = group.checked_mul(100).ok_or(ParseNumberError::OutOfRange)?;
+        } else if let Some(scale) = SCALES.iter().position(|word| !word.is_empty() && *word == token) {
+            let value = 1000u128.checked_pow(scale as u32)
+                .and_then(|power| power.checked_mul(group))
+                .ok_or(ParseNumberError::OutOfRange)?;
+            total = total.checked_add(value).ok_or(ParseNumberError::OutOfRange)?;
+            group = 0;
+        } else {
+            return Err(ParseNumberError::InvalidWord(pos));
+        }
+    }
+    total = total.checked_add(group).ok_or(ParseNumberError::OutOfRange)?;
+
+    let value = from_parts::<T>(total, negative)?;
+    let normalized = lowered.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if to_words(value) != normalized {
+        return Err(ParseNumberError::NonCanonical);
+    }
+    return Ok(value);
+}
+
+// ----------- DIGIT GROUPING ------------
+
+// 1234567 with ',' becomes "1,234,567"
+pub fn format_grouped<T: Int>(num: T, separator: char) -> String {
+    let mut text = String::new();
+    if num.is_negative() {
+        text.push('-');
+    }
+    for (i, group) in digits(num, 1000).enumerate() {
+        if i == 0 {
+            text.push_str(&group.to_string());
+        } else {
+            text.push(separator);
+            text.push_str(&format!("{:03}", group));
+        }
+    }
+    return text;
+}
+
+pub fn parse_grouped<T: Int>(text: &str, separator: char) -> Result<T, ParseNumberError> {
+    let (negative, body, offset) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest, 1),
+        None => (false, text, 0)
+    };
+    if body.is_empty() {
+        return Err(ParseNumberError::Empty);
+    }
+
+    let mut magnitude: u128 = 0;
+    let mut group_len = 0;
+    let mut first_group = true;
+    for (pos, c) in body.char_indices() {
+        if c == separator {
+            if group_len == 0 || (first_group && group_len > 3) || (!first_group && group_len != 3) {
+                return Err(ParseNumberError::InvalidGrouping(offset + pos));
+            }
+            first_group = false;
+            group_len = 0;
+            continue;
+        }
+        let digit = c.to_digit(10).ok_or(ParseNumberError::InvalidCharacter(offset + pos))?;
+        if first_group && group_len == 0 && digit == 0 && body.len() > 1 {
+            return Err(ParseNumberError::NonCanonical);
+        }
+        magnitude = magnitude.checked_mul(10)
+            .and_then(|value| value.checked_add(digit as u128))
+            .ok_or(ParseNumberError::OutOfRange)?;
+        group_len += 1;
+    }
+    if (first_group && group_len > 3) || (!first_group && group_len != 3) {
+        return Err(ParseNumberError::InvalidGrouping(text.len()));
+    }
+    return from_parts(magnitude, negative);
+}
+
+// ----------- SCIENTIFIC NOTATION ------------
+
+// Rounds half away from zero to the given number of significant digits,
+// returns the digits and the power of ten they are scaled by
+fn round_significant(value: u128, len: usize, keep: usize) -> (Vec<u32>, usize) {
+    if len <= keep {
+        return (digits(value, 10).collect(), 0);
+    }
+    let shift = len - keep;
+    let divisor = 10u128.pow(shift as u32);
+    let mut kept = value / divisor;
+    if value % divisor >= divisor - value % divisor {
+        kept += 1;
+    }
+    return (digits(kept, 10).collect(), shift);
+}
+
+fn format_notation<T: Int>(num: T, precision: Option<usize>, engineering: bool) -> String {
+    let value = num.magnitude();
+    let len = digits(value, 10).len();
+    let int_len = |exponent: usize| if engineering { exponent % 3 + 1 } else { 1 };
+
+    let (digits, shift) = match precision {
+        Some(precision) => round_significant(value, len, int_len(len - 1) + precision),
+        None => (digits(value, 10).collect(), 0)
+    };
+    // rounding can carry into a new leading digit, so the exponent is taken afterwards
+    let exponent = digits.len() - 1 + shift;
+    let int_len = int_len(exponent);
+    let digit_at = |i: usize| digits.get(i).copied().unwrap_or(0);
+
+    let mut text = String::new();
+    if num.is_negative() {
+        text.push('-');
+    }
+    for i in 0..int_len {
+        text.push(char::from_digit(digit_at(i), 10).unwrap());
+    }
+    let fraction_len = match precision {
+        Some(precision) => precision,
+        None => digits.iter().rposition(|digit| *digit != 0).map_or(0, |last| last + 1).saturating_sub(int_len)
+    };
+    if fraction_len > 0 {
+        text.push('.');
+        for i in int_len..int_len + fraction_len {
+            text.push(char::from_digit(digit_at(i), 10).unwrap());
+        }
+    }
+    text.push_str(&format!("e{}", exponent - (int_len - 1)));
+    return text;
+}
+
+// Exact without a precision, otherwise rounded to that many fraction digits:
+// 1234500 is "1.2345e6", or "1.23e6" with precision 2
+pub fn to_scientific<T: Int>(num: T, precision: Option<usize>) -> String {
+    return format_notation(num, precision, false);
+}
+
+// Like to_scientific with exponents that are multiples of three: 12345 is "12.345e3"
+pub fn to_engineering<T: Int>(num: T, precision: Option<usize>) -> String {
+    return format_notation(num, precision, true);
+}
+
+// Reads both notations, as long as the value is an integer: "1.5e3", "-12e-1" fails
+pub fn from_scientific<T: Int>(text: &str) -> Result<T, ParseNumberError> {
+    let (negative, body, offset) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest, 1),
+        None => (false, text, 0)
+    };
+    if body.is_empty() {
+        return Err(ParseNumberError::Empty);
+    }
+
+    let (mantissa, exponent_text, exponent_offset) = match body.find(|c| c == 'e' || c == 'E') {
+        Some(pos) => (&body[..pos], &body[pos + 1..], offset + pos + 1),
+        None => (body, "0", 0)
+    };
+
+    let mut value: u128 = 0;
+    let mut fraction_len: i64 = 0;
+    let mut seen_point = false;
+    let mut seen_digit = false;
+    for (pos, c) in mantissa.char_indices() {
+        if c == '.' && !seen_point {
+            seen_point = true;
+            continue;
+        }
+        let digit = c.to_digit(10).ok_or(ParseNumberError::InvalidCharacter(offset + pos))?;
+        seen_digit = true;
+        value = value.checked_mul(10)
+            .and_then(|value| value.checked_add(digit as u128))
+            .ok_or(ParseNumberError::OutOfRange)?;
+        if seen_point {
+            fraction_len += 1;
+        }
+    }
+    if !seen_digit {
+        return Err(ParseNumberError::InvalidCharacter(offset));
+    }
+
+    let exponent: i64 = match exponent_text.parse() {
+        Ok(exponent) => exponent,
+        Err(_) => return Err(ParseNumberError::InvalidCharacter(exponent_offset))
+    };
+    let scale = exponent.checked_sub(fraction_len).ok_or(ParseNumberError::OutOfRange)?;
+    if scale >= 0 {
+        if value != 0 {
+            let power = 10u128.checked_pow(scale.min(u32::MAX as i64) as u32)
+                .ok_or(ParseNumberError::OutOfRange)?;
+            value = value.checked_mul(power).ok_or(ParseNumberError::OutOfRange)?;
+        }
+    } else {
+        for _ in 0..scale.checked_neg().ok_or(ParseNumberError::OutOfRange)? {
+            if value % 10 != 0 {
+                return Err(ParseNumberError::NotAnInteger);
+            }
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+    }
+    return from_parts(value, negative);
+}
+
+#[test]
+fn test_digits() {
+    assert_eq!(digits(0u8, 10).collect::<Vec<u32>>(), vec![0]);
+    assert_eq!(digits(1234, 10).collect::<Vec<u32>>(), vec![1, 2, 3, 4]);
+    assert_eq!(digits(-1234, 10).rev().collect::<Vec<u32>>(), vec![4, 3, 2, 1]);
+    assert_eq!(digits(0b1011u8, 2).collect::<Vec<u32>>(), vec![1, 0, 1, 1]);
+    assert_eq!(digits(255u8, 16).collect::<Vec<u32>>(), vec![15, 15]);
+    assert_eq!(digits(i64::MIN, 10).len(), 19);
+    assert_eq!(digits(u128::MAX, 10).len(), 39);
+    assert_eq!(digits(u128::MAX, 10).map(|digit| digit as u128).sum::<u128>(), 165);
+
+    // taking from both ends meets in the middle
+    let mut iter = digits(123456789, 10);
+    assert_eq!((iter.next(), iter.next_back(), iter.len()), (Some(1), Some(9), 7));
+    assert_eq!((iter.next_back(), iter.next(), iter.next_back()), (Some(8), Some(2), Some(7)));
+    assert_eq!(iter.collect::<Vec<u32>>(), vec![3, 4, 5, 6]);
+
+    for num in 0..5000 {
+        let sum: u32 = digits(num, 10).sum();
+        assert_eq!(sum as i32, crate::digit_sum(num));
+        let value = digits(num, 7).fold(0, |value, digit| value * 7 + digit as i32);
+        assert_eq!(value, num);
+    }
+}
+
+#[test]
+fn test_roman() {
+    assert_eq!(to_roman(1).as_deref(), Some("I"));
+    assert_eq!(to_roman(4).as_deref(), Some("IV"));
+    assert_eq!(to_roman(1994).as_deref(), Some("MCMXCIV"));
+    assert_eq!(to_roman(3888).as_deref(), Some("MMMDCCCLXXXVIII"));
+    assert_eq!(to_roman(3999).as_deref(), Some("MMMCMXCIX"));
+    assert_eq!(to_roman(0), None);
+    assert_eq!(to_roman(4000), None);
+
+    for num in 1..4000 {
+        assert_eq!(from_roman(&to_roman(num).unwrap()), Ok(num));
+    }
+    assert_eq!(from_roman(""), Err(ParseNumberError::Empty));
+    assert_eq!(from_roman("MCMXCIVA"), Err(ParseNumberError::InvalidCharacter(7)));
+    assert_eq!(from_roman("XIi"), Err(ParseNumberError::InvalidCharacter(2)));
+    assert_eq!(from_roman("IIII"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_roman("IM"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_roman("VX"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_roman("IIX"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_roman("MMMM"), Err(ParseNumberError::NonCanonical));
+}
+
+#[test]
+fn test_words() {
+    assert_eq!(to_words(0), "zero");
+    assert_eq!(to_words(13u8), "thirteen");
+    assert_eq!(to_words(-42), "negative forty-two");
+    assert_eq!(to_words(100), "one hundred");
+    assert_eq!(to_words(1105), "one thousand one hundred five");
+    assert_eq!(to_words(1_000_020), "one million twenty");
+    assert_eq!(to_words(i64::MIN), "negative nine quintillion two hundred twenty-three quadrillion \
+        three hundred seventy-two trillion thirty-six billion eight hundred fifty-four million \
+        seven hundred seventy-five thousand eight hundred eight");
+    assert!(to_words(u128::MAX).starts_with("three hundred forty undecillion"));
+
+    for num in (-3000..3000).chain((0..1000).map(|i| i * 7_654_321)) {
+        assert_eq!(from_words::<i64>(&to_words(num)), Ok(num));
+    }
+    assert_eq!(from_words::<u128>(&to_words(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(from_words::<i8>(&to_words(i8::MIN)), Ok(i8::MIN));
+    assert_eq!(from_words::<i32>("  Negative   Forty-Two "), Ok(-42));
+
+    assert_eq!(from_words::<i32>(""), Err(ParseNumberError::Empty));
+    assert_eq!(from_words::<i32>("one hundred fourty"), Err(ParseNumberError::InvalidWord(12)));
+    assert_eq!(from_words::<i32>("forty two"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_words::<i32>("one hundred and five"), Err(ParseNumberError::InvalidWord(12)));
+    assert_eq!(from_words::<i32>("twelve hundred"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_words::<i32>("negative zero"), Err(ParseNumberError::NonCanonical));
+    assert_eq!(from_words::<u8>("two hundred fifty-six"), Err(ParseNumberError::OutOfRange));
+    assert_eq!(from_words::<u32>("negative one"), Err(ParseNumberError::OutOfRange));
+    // repeated scales grow the group past u128 before the canonical check
+    assert_eq!(from_words::<u128>(&format!("one{}", " hundred".repeat(20))), Err(ParseNumberError::OutOfRange));
+    assert_eq!(from_words::<u128>(&format!("one{}", " hundred".repeat(19))), Err(ParseNumberError::NonCanonical));
+}
+
+#[test]
+fn test_grouped() {
+    assert_eq!(format_grouped(0, ','), "0");
+    assert_eq!(format_grouped(999, ','), "999");
+    assert_eq!(format_grouped(1234567, ','), "1,234,567");
+    assert_eq!(format_grouped(-1000, ' '), "-1 000");
+    assert_eq!(format_grouped(1_000_001u64, '.'), "1.000.001");
+    assert_eq!(format_grouped(i128::MIN, '_'), "-170_141_183_460_469_231_731_687_303_715_884_105_728");
+
+    for num in (-5000..5000).chain((0..1000).map(|i| i * 987_654_321)) {
+        assert_eq!(parse_grouped::<i64>(&format_grouped(num, ','), ','), Ok(num));
+    }
+    assert_eq!(parse_grouped::<i128>(&format_grouped(i128::MIN, '_'), '_'), Ok(i128::MIN));
+    assert_eq!(parse_grouped::<u8>("255", ','), Ok(255));
+
+    assert_eq!(parse_grouped::<i32>("", ','), Err(ParseNumberError::Empty));
+    assert_eq!(parse_grouped::<i32>("-", ','), Err(ParseNumberError::Empty));
+    assert_eq!(parse_grouped::<i32>("1,2a4", ','), Err(ParseNumberError::InvalidCharacter(3)));
+    assert_eq!(parse_grouped::<i32>("1234", ','), Err(ParseNumberError::InvalidGrouping(4)));
+    assert_eq!(parse_grouped::<i32>("1,23,456", ','), Err(ParseNumberError::InvalidGrouping(4)));
+    assert_eq!(parse_grouped::<i32>("1234,567", ','), Err(ParseNumberError::InvalidGrouping(4)));
+    assert_eq!(parse_grouped::<i32>(",123", ','), Err(ParseNumberError::InvalidGrouping(0)));
+    assert_eq!(parse_grouped::<i32>("012", ','), Err(ParseNumberError::NonCanonical));
+    assert_eq!(parse_grouped::<u8>("256", ','), Err(ParseNumberError::OutOfRange));
+    assert_eq!(parse_grouped::<u8>("-1", ','), Err(ParseNumberError::OutOfRange));
+}
+
+#[test]
+fn test_scientific() {
+    assert_eq!(to_scientific(0, None), "0e0");
+    assert_eq!(to_scientific(7, None), "7e0");
+    assert_eq!(to_scientific(1234500, None), "1.2345e6");
+    assert_eq!(to_scientific(-1000, None), "-1e3");
+    assert_eq!(to_scientific(1234500, Some(2)), "1.23e6");
+    assert_eq!(to_scientific(1235, Some(2)), "1.24e3");
+    assert_eq!(to_scientific(9996, Some(2)), "1.00e4");
+    assert_eq!(to_scientific(12, Some(3)), "1.200e1");
+    assert_eq!(to_scientific(0, Some(1)), "0.0e0");
+    assert_eq!(to_scientific(u128::MAX, Some(3)), "3.403e38");
+
+    assert_eq!(to_engineering(0, None), "0e0");
+    assert_eq!(to_engineering(100, None), "100e0");
+    assert_eq!(to_engineering(12345, None), "12.345e3");
+    assert_eq!(to_engineering(-1234500, None), "-1.2345e6");
+    assert_eq!(to_engineering(12345, Some(1)), "12.3e3");
+    assert_eq!(to_engineering(999_950, Some(1)), "1.0e6");
+    assert_eq!(to_engineering(999_949, Some(1)), "999.9e3");
+
+    for num in (-3000..3000).chain((0..1000).map(|i| i * 123_456_789)) {
+        assert_eq!(from_scientific::<i64>(&to_scientific(num, None)), Ok(num));
+        assert_eq!(from_scientific::<i64>(&to_engineering(num, None)), Ok(num));
+    }
+    assert_eq!(from_scientific::<u128>(&to_scientific(u128::MAX, None)), Ok(u128::MAX));
+    assert_eq!(from_scientific::<i32>(&to_scientific(1234500, Some(2))), Ok(1230000));
+    assert_eq!(from_scientific::<i32>("1200e-2"), Ok(12));
+    assert_eq!(from_scientific::<i32>("1.5E3"), Ok(1500));
+    assert_eq!(from_scientific::<i32>("42"), Ok(42));
+    assert_eq!(from_scientific::<i32>("0e99999"), Ok(0));
+
+    assert_eq!(from_scientific::<i32>(""), Err(ParseNumberError::Empty));
+    assert_eq!(from_scientific::<i32>("-12e-1"), Err(ParseNumberError::NotAnInteger));
+    assert_eq!(from_scientific::<i32>("1.2.3e4"), Err(ParseNumberError::InvalidCharacter(3)));
+    assert_eq!(from_scientific::<i32>("1.2ex"), Err(ParseNumberError::InvalidCharacter(4)));
+    assert_eq!(from_scientific::<i32>(".e1"), Err(ParseNumberError::InvalidCharacter(0)));
+    assert_eq!(from_scientific::<i32>("3e9"), Err(ParseNumberError::OutOfRange));
+    assert_eq!(from_scientific::<u64>("-1e0"), Err(ParseNumberError::OutOfRange));
+
+    // the exponent minus the fraction digits does not fit an i64
+    assert_eq!(from_scientific::<i32>("1.0e-9223372036854775808"), Err(ParseNumberError::OutOfRange));
+    assert_eq!(from_scientific::<i32>("1e-9223372036854775808"), Err(ParseNumberError::OutOfRange));
+    assert_eq!(from_scientific::<i32>("10e-9223372036854775807"), Err(ParseNumberError::NotAnInteger));
+}
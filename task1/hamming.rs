@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use crate::int::Int;
+
+pub fn hamming_distance<T: Int>(a: T, b: T) -> u32 {
+    return (a.to_bits() ^ b.to_bits()).count_ones();
+}
+
+// None when the slices have different lengths
+pub fn hamming_distance_bytes(a: &[u8], b: &[u8]) -> Option<u32> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    let mut distance = 0;
+    let mut chunks_a = a.chunks_exact(8);
+    let mut chunks_b = b.chunks_exact(8);
+    for (chunk_a, chunk_b) in (&mut chunks_a).zip(&mut chunks_b) {
+        let word_a = u64::from_le_bytes(chunk_a.try_into().unwrap());
+        let word_b = u64::from_le_bytes(chunk_b.try_into().unwrap());
+        distance += (word_a ^ word_b).count_ones();
+    }
+    for (byte_a, byte_b) in chunks_a.remainder().iter().zip(chunks_b.remainder()) {
+        distance += (byte_a ^ byte_b).count_ones();
+    }
+    return Some(distance);
+}
+
+// Bitsets are given as words, missing words of the shorter one count as zero.
+// Two empty sets are considered equal.
+pub fn jaccard(a: &[u64], b: &[u64]) -> f64 {
+    let mut common = 0;
+    let mut total = 0;
+    for i in 0..a.len().max(b.len()) {
+        let word_a = a.get(i).copied().unwrap_or(0);
+        let word_b = b.get(i).copied().unwrap_or(0);
+        common += (word_a & word_b).count_ones();
+        total += (word_a | word_b).count_ones();
+    }
+    if total == 0 {
+        return 1.0;
+    }
+    return common as f64 / total as f64;
+}
+
+// Indices of all fingerprints within distance k of the query
+pub fn within_distance(fingerprints: &[u64], query: u64, k: u32) -> Vec<usize> {
+    let mut found = vec![];
+    for (i, fingerprint) in fingerprints.iter().enumerate() {
+        if hamming_distance(*fingerprint, query) <= k {
+            found.push(i);
+        }
+    }
+    return found;
+}
+
+// ----------- MULTI-INDEX HASHING ------------
+
+// The fingerprints are cut into max_distance + 1 blocks. Two fingerprints
+// within max_distance of each other differ in at most max_distance blocks,
+// so by the pigeonhole principle at least one block is equal. Every block
+// has its own table, the candidates found there are checked exactly. There
+// are at most 64 one bit blocks, larger distances are not indexed.
+pub struct MultiIndex {
+    fingerprints: Vec<u64>,
+    max_distance: u32,
+    // (shift, width) of every block
+    blocks: Vec<(u32, u32)>,
+    tables: Vec<HashMap<u64, Vec<usize>>>
+}
+
+fn block_value(fingerprint: u64, (shift, width): (u32, u32)) -> u64 {
+    return (fingerprint >> shift) & (u64::MAX >> (64 - width));
+}
+
+impl MultiIndex {
+    pub fn new(fingerprints: &[u64], max_distance: u32) -> MultiIndex {
+        let count = max_distance.saturating_add(1).min(64);
+        let mut blocks = vec![];
+        let mut shift = 0;
+        for i in 0..count {
+            let width = 64 / count + (i < 64 % count) as u32;
+            blocks.push((shift, width));
+            shift += width;
+        }
+
+        let mut tables: Vec<HashMap<u64, Vec<usize>>> = vec![HashMap::new(); blocks.len()];
+        for (i, fingerprint) in fingerprints.iter().enumerate() {
+            for (table, block) in tables.iter_mut().zip(&blocks) {
+                table.entry(block_value(*fingerprint, *block)).or_default().push(i);
+            }
+        }
+
+        return MultiIndex {
+            fingerprints: fingerprints.to_vec(),
+            max_distance,
+            blocks,
+            tables
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.fingerprints.len();
+    }
+
+    // Distances above max_distance, or from 64 on where there are fewer blocks
+    // than k + 1, fall back to a linear scan
+    pub fn search(&self, query: u64, k: u32) -> Vec<usize> {
+        if k > self.max_distance || k >= self.blocks.len() as u32 {
+            return within_distance(&self.fingerprints, query, k);
+        }
+
+        let mut candidates = vec![];
+        for (table, block) in self.tables.iter().zip(&self.blocks) {
+            if let Some(ids) = table.get(&block_value(query, *block)) {
+                candidates.extend_from_slice(ids);
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.retain(|i| hamming_distance(self.fingerprints[*i], query) <= k);
+        return candidates;
+    }
+}
+
+#[test]
+fn test_distances() {
+    assert_eq!(hamming_distance(0u8, 0u8), 0);
+    assert_eq!(hamming_distance(0b1011u32, 0b0110u32), 3);
+    assert_eq!(hamming_distance(-1i16, 0), 16);
+    assert_eq!(hamming_distance(u128::MAX, 1), 127);
+
+    assert_eq!(hamming_distance_bytes(b"karolin", b"kathrin"), Some(9));
+    assert_eq!(hamming_distance_bytes(b"", b""), Some(0));
+    assert_eq!(hamming_distance_bytes(b"abc", b"ab"), None);
+
+    let a: Vec<u8> = (0..=255).collect();
+    let b: Vec<u8> = (0..=255).map(|x: u8| x.rotate_left(3)).collect();
+    let naive: u32 = a.iter().zip(&b).map(|(x, y)| hamming_distance(*x, *y)).sum();
+    assert_eq!(hamming_distance_bytes(&a, &b), Some(naive));
+}
+
+#[test]
+fn test_jaccard() {
+    assert_eq!(jaccard(&[], &[]), 1.0);
+    assert_eq!(jaccard(&[0b1111], &[0b1111]), 1.0);
+    assert_eq!(jaccard(&[0b0011], &[0b1100]), 0.0);
+    assert_eq!(jaccard(&[0b0111], &[0b1110]), 0.5);
+    assert_eq!(jaccard(&[0b1, 0b1], &[0b1]), 0.5);
+    assert_eq!(jaccard(&[0, u64::MAX], &[u64::MAX, u64::MAX]), 0.5);
+}
+
+#[test]
+fn test_multi_index() {
+    let mut state: u64 = 0x9E3779B97F4A7C15;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        return state;
+    };
+
+    // random fingerprints with clusters of near duplicates around a few of them
+    let mut fingerprints = vec![];
+    for _ in 0..500 {
+        fingerprints.push(next());
+    }
+    for i in 0..200 {
+        let base = fingerprints[i % 10];
+        let flips = next() % 12;
+        let mut noisy = base;
+        for _ in 0..flips {
+            noisy ^= 1 << (next() % 64);
+        }
+        fingerprints.push(noisy);
+    }
+
+    for max_distance in [0, 3, 7, 10] {
+        let index = MultiIndex::new(&fingerprints, max_distance);
+        assert_eq!(index.len(), fingerprints.len());
+        for q in 0..30 {
+            let query = if q < 20 { fingerprints[q] ^ (1 << q) } else { next() };
+            for k in 0..=max_distance + 2 {
+                assert_eq!(index.search(query, k), within_distance(&fingerprints, query, k));
+            }
+        }
+    }
+
+    let index = MultiIndex::new(&[0, 1, 3, 7, u64::MAX], 2);
+    assert_eq!(index.search(0, 0), vec![0]);
+    assert_eq!(index.search(0, 2), vec![0, 1, 2]);
+    assert_eq!(index.search(0, 64), vec![0, 1, 2, 3, 4]);
+    assert_eq!(MultiIndex::new(&[], 4).search(0, 4), Vec::<usize>::new());
+}
+
+#[test]
+fn test_multi_index_large_distances() {
+    // 64 one bit blocks can not cover 65 differing bits
+    assert_eq!(MultiIndex::new(&[u64::MAX], 64).search(0, 64), vec![0]);
+    assert_eq!(MultiIndex::new(&[u64::MAX], 64).search(0, 63), Vec::<usize>::new());
+    assert_eq!(MultiIndex::new(&[u64::MAX, 0, 1 << 63], 100).search(0, 70), vec![0, 1, 2]);
+    assert_eq!(MultiIndex::new(&[u64::MAX, 0], u32::MAX).search(u64::MAX, u32::MAX), vec![0, 1]);
+    assert_eq!(MultiIndex::new(&[u64::MAX, 0], u32::MAX).search(u64::MAX, 3), vec![0]);
+}
@@ -0,0 +1,78 @@
+// Common view of the primitive integer types: every value is either split
+// into a sign and a magnitude, or taken as raw bits, both fit into u128.
+pub trait Int: Copy + PartialEq + PartialOrd {
+    const BITS: u32;
+    const SIGNED: bool;
+
+    fn is_negative(self) -> bool;
+    fn magnitude(self) -> u128;
+    fn from_magnitude(magnitude: u128, negative: bool) -> Self;
+    // raw two's complement bits, zero extended
+    fn to_bits(self) -> u128;
+    fn from_bits(bits: u128) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const BITS: u32 = <$t>::BITS;
+            const SIGNED: bool = false;
+
+            fn is_negative(self) -> bool {
+                return false;
+            }
+
+            fn magnitude(self) -> u128 {
+                return self as u128;
+            }
+
+            fn from_magnitude(magnitude: u128, _negative: bool) -> Self {
+                return magnitude as $t;
+            }
+
+            fn to_bits(self) -> u128 {
+                return self as u128;
+            }
+
+            fn from_bits(bits: u128) -> Self {
+                return bits as $t;
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty => $u:ty),*) => {$(
+        impl Int for $t {
+            const BITS: u32 = <$t>::BITS;
+            const SIGNED: bool = true;
+
+            fn is_negative(self) -> bool {
+                return self < 0;
+            }
+
+            fn magnitude(self) -> u128 {
+                return self.unsigned_abs() as u128;
+            }
+
+            fn from_magnitude(magnitude: u128, negative: bool) -> Self {
+                let value = magnitude as $t;
+                if negative {
+                    return value.wrapping_neg();
+                }
+                return value;
+            }
+
+            fn to_bits(self) -> u128 {
+                return self as $u as u128;
+            }
+
+            fn from_bits(bits: u128) -> Self {
+                return bits as $t;
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
@@ -0,0 +1,337 @@
+use std::ops::Range;
+use crate::int::Int;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PalindromeOptions {
+    pub fold_case: bool,
+    pub fold_punctuation: bool
+}
+
+pub fn is_palindrome<T: PartialEq>(items: &[T]) -> bool {
+    let len = items.len();
+    for i in 0..len / 2 {
+        if items[i] != items[len - i - 1] {
+            return false;
+        }
+    }
+    return true;
+}
+
+// ----------- GRAPHEMES ------------
+
+fn is_extending(c: char) -> bool {
+    return matches!(c,
+        '\u{0300}'..='\u{036F}' | '\u{0483}'..='\u{0489}' | '\u{0591}'..='\u{05BD}'
+        | '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0900}'..='\u{0903}'
+        | '\u{093A}'..='\u{094F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200C}' | '\u{20D0}'..='\u{20FF}' | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}'
+        | '\u{E0100}'..='\u{E01EF}');
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    return ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+}
+
+// Simplified extended grapheme clusters: a base character followed by its
+// combining marks, variation selectors and emoji modifiers, zero width joiner
+// sequences, pairs of regional indicators and CR LF.
+pub fn graphemes(text: &str) -> Vec<&str> {
+    let mut clusters = vec![];
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    let mut indicators = 0;
+
+    for (i, c) in text.char_indices() {
+        let joined = match prev {
+            None => true,
+            Some(p) => is_extending(c) || c == '\u{200D}' || p == '\u{200D}'
+                || (p == '\r' && c == '\n')
+                || (is_regional_indicator(c) && indicators % 2 == 1)
+        };
+        if !joined {
+            clusters.push(&text[start..i]);
+            start = i;
+            indicators = 0;
+        }
+        if is_regional_indicator(c) {
+            indicators += 1;
+        }
+        prev = Some(c);
+    }
+    if start < text.len() {
+        clusters.push(&text[start..]);
+    }
+    return clusters;
+}
+
+fn is_punctuation(c: char) -> bool {
+    return c.is_whitespace() || c.is_ascii_punctuation()
+        || matches!(c, '\u{00A1}' | '\u{00A7}' | '\u{00AB}' | '\u{00B6}' | '\u{00B7}'
+                       | '\u{00BB}' | '\u{00BF}' | '\u{2010}'..='\u{2027}'
+                       | '\u{2030}'..='\u{205E}' | '\u{3000}'..='\u{303F}');
+}
+
+pub fn is_palindrome_str(text: &str, options: PalindromeOptions) -> bool {
+    let mut clusters: Vec<String> = vec![];
+    for cluster in graphemes(text) {
+        let base = cluster.chars().next().unwrap();
+        if options.fold_punctuation && is_punctuation(base) {
+            continue;
+        }
+        if options.fold_case {
+            clusters.push(cluster.to_lowercase());
+        } else {
+            clusters.push(cluster.to_string());
+        }
+    }
+    return is_palindrome(&clusters);
+}
+
+// ----------- MANACHER ------------
+
+// Longest palindromic run in O(n), the first one wins on ties
+pub fn longest_palindrome<T: PartialEq>(items: &[T]) -> Range<usize> {
+    // radius[i] is the palindrome radius around position i of the virtual
+    // sequence with separators between items: even indices are gaps
+    let len = 2 * items.len() + 1;
+    let mut radius = vec![0usize; len];
+    let (mut center, mut right) = (0usize, 0usize);
+    let mut best = 0..0;
+
+    for i in 0..len {
+        let mut r = 0;
+        if i < right {
+            r = radius[2 * center - i].min(right - i);
+        }
+        while i >= r + 1 && i + r + 1 < len
+            && ((i + r + 1) % 2 == 0 || items[(i - r - 1) / 2] == items[(i + r + 1) / 2]) {
+            r += 1;
+        }
+        radius[i] = r;
+        if i + r > right {
+            center = i;
+            right = i + r;
+        }
+        if r > best.end - best.start {
+            best = (i - r) / 2..(i + r) / 2;
+        }
+    }
+    return best;
+}
+
+pub fn longest_palindromic_substring(text: &str) -> &str {
+    let clusters = graphemes(text);
+    let range = longest_palindrome(&clusters);
+    if range.is_empty() {
+        return "";
+    }
+    let start = clusters[range.start].as_ptr() as usize - text.as_ptr() as usize;
+    let last = clusters[range.end - 1];
+    let end = last.as_ptr() as usize - text.as_ptr() as usize + last.len();
+    return &text[start..end];
+}
+
+// ----------- NUMBERS ------------
+
+fn to_digits(num: u128, base: u32) -> Vec<u32> {
+    let mut digits = vec![];
+    let mut rest = num;
+    loop {
+        digits.push((rest % base as u128) as u32);
+        rest = rest / base as u128;
+        if rest == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    return digits;
+}
+
+fn from_digits(digits: &[u32], base: u32) -> Option<u64> {
+    let mut num: u64 = 0;
+    for digit in digits {
+        num = num.checked_mul(base as u64)?.checked_add(*digit as u64)?;
+    }
+    return Some(num);
+}
+
+fn mirror(digits: &mut [u32]) {
+    let len = digits.len();
+    for i in 0..len / 2 {
+        digits[len - i - 1] = digits[i];
+    }
+}
+
+pub fn is_palindrome_in_base<T: Int>(num: T, base: u32) -> bool {
+    if num.is_negative() || base < 2 {
+        return false;
+    }
+    return is_palindrome(&to_digits(num.magnitude(), base));
+}
+
+// Smallest palindrome in the given base that is greater than num
+pub fn next_palindrome(num: u64, base: u32) -> Option<u64> {
+    if base < 2 {
+        return None;
+    }
+
+    let target = to_digits(num.checked_add(1)? as u128, base);
+    let mut digits = target.clone();
+    mirror(&mut digits);
+    if digits >= target {
+        return from_digits(&digits, base);
+    }
+
+    // the left half is too small: increment it, middle digit included
+    let mut i = (digits.len() - 1) / 2;
+    loop {
+        if digits[i] + 1 < base {
+            digits[i] += 1;
+            break;
+        }
+        digits[i] = 0;
+        if i == 0 {
+            // all digits were base - 1, continue with 10...01
+            let mut longer = vec![0; digits.len() + 1];
+            longer[0] = 1;
+            longer[digits.len()] = 1;
+            return from_digits(&longer, base);
+        }
+        i -= 1;
+    }
+    mirror(&mut digits);
+    return from_digits(&digits, base);
+}
+
+// Largest palindrome in the given base that is less than num
+pub fn prev_palindrome(num: u64, base: u32) -> Option<u64> {
+    if base < 2 || num == 0 {
+        return None;
+    }
+
+    let target = to_digits(num as u128 - 1, base);
+    let mut digits = target.clone();
+    mirror(&mut digits);
+    if digits <= target {
+        return from_digits(&digits, base);
+    }
+
+    // the left half is too large: decrement it, middle digit included
+    let mut i = (digits.len() - 1) / 2;
+    while digits[i] == 0 {
+        digits[i] = base - 1;
+        i -= 1;
+    }
+    digits[i] -= 1;
+    if digits[0] == 0 {
+        // dropped below the shortest number of this length, continue with 99...9
+        return from_digits(&vec![base - 1; digits.len() - 1], base);
+    }
+    mirror(&mut digits);
+    return from_digits(&digits, base);
+}
+
+#[test]
+fn test_slices() {
+    assert_eq!(is_palindrome::<i32>(&[]), true);
+    assert_eq!(is_palindrome(&[1]), true);
+    assert_eq!(is_palindrome(&[1, 2, 1]), true);
+    assert_eq!(is_palindrome(&[1, 2, 2, 1]), true);
+    assert_eq!(is_palindrome(&[1, 2, 3, 1]), false);
+    assert_eq!(is_palindrome(&["ab", "cd", "ab"]), true);
+}
+
+#[test]
+fn test_strings() {
+    let exact = PalindromeOptions::default();
+    let folded = PalindromeOptions { fold_case: true, fold_punctuation: true };
+
+    assert_eq!(is_palindrome_str("", exact), true);
+    assert_eq!(is_palindrome_str("racecar", exact), true);
+    assert_eq!(is_palindrome_str("Racecar", exact), false);
+    assert_eq!(is_palindrome_str("Racecar", PalindromeOptions { fold_case: true, ..exact }), true);
+    assert_eq!(is_palindrome_str("A man, a plan, a canal: Panama", exact), false);
+    assert_eq!(is_palindrome_str("A man, a plan, a canal: Panama", folded), true);
+    assert_eq!(is_palindrome_str("Was it a car or a cat I saw?", folded), true);
+    assert_eq!(is_palindrome_str("¿Sé verlas al revés?", folded), true);
+    assert_eq!(is_palindrome_str("Hello, world", folded), false);
+
+    // combining acute accents stay attached to their base letter
+    assert_eq!(is_palindrome_str("e\u{301}te\u{301}", exact), true);
+    assert_eq!("e\u{301}te\u{301}".chars().rev().collect::<String>() == "e\u{301}te\u{301}", false);
+    assert_eq!(is_palindrome_str("ÉTÉ", PalindromeOptions { fold_case: true, ..exact }), true);
+
+    // emoji sequences are single clusters
+    assert_eq!(graphemes("👨‍👩‍👧x👍🏽").len(), 3);
+    assert_eq!(is_palindrome_str("👨‍👩‍👧x👨‍👩‍👧", exact), true);
+    assert_eq!(is_palindrome_str("🇺🇦🇺🇦", exact), true);
+    assert_eq!(is_palindrome_str("🇺🇦🇦🇺", exact), false);
+    assert_eq!(graphemes("a\r\nb"), vec!["a", "\r\n", "b"]);
+}
+
+#[test]
+fn test_longest_palindrome() {
+    assert_eq!(longest_palindromic_substring(""), "");
+    assert_eq!(longest_palindromic_substring("a"), "a");
+    assert_eq!(longest_palindromic_substring("babad"), "bab");
+    assert_eq!(longest_palindromic_substring("cbbd"), "bb");
+    assert_eq!(longest_palindromic_substring("forgeeksskeegfor"), "geeksskeeg");
+    assert_eq!(longest_palindromic_substring("xe\u{301}ye\u{301}z"), "e\u{301}ye\u{301}");
+    assert_eq!(longest_palindrome(&[1, 2, 3, 2, 1, 5]), 0..5);
+
+    // compare with the cubic brute force on every word over a small alphabet
+    for word in 0..3u32.pow(9) {
+        let mut items = vec![];
+        let mut rest = word;
+        for _ in 0..9 {
+            items.push(rest % 3);
+            rest /= 3;
+        }
+        let mut best = 0;
+        for start in 0..items.len() {
+            for end in start + 1..=items.len() {
+                if is_palindrome(&items[start..end]) {
+                    best = best.max(end - start);
+                }
+            }
+        }
+        let range = longest_palindrome(&items);
+        assert_eq!(range.len(), best);
+        assert!(is_palindrome(&items[range]));
+    }
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(is_palindrome_in_base(0u8, 2), true);
+    assert_eq!(is_palindrome_in_base(9u32, 2), true);
+    assert_eq!(is_palindrome_in_base(10u32, 2), false);
+    assert_eq!(is_palindrome_in_base(0xABAu16, 16), true);
+    assert_eq!(is_palindrome_in_base(12321i64, 10), true);
+    assert_eq!(is_palindrome_in_base(-121i64, 10), false);
+    assert_eq!(is_palindrome_in_base(u128::MAX, 2), true);
+    assert_eq!(is_palindrome_in_base(121, 1), false);
+
+    for base in [2, 3, 10, 16] {
+        let palindromes: Vec<u64> = (0..6000u64)
+            .filter(|num| is_palindrome_in_base(*num, base))
+            .collect();
+        for num in 0..palindromes[palindromes.len() - 1] {
+            let next = palindromes.iter().find(|p| **p > num).copied();
+            let prev = palindromes.iter().rev().find(|p| **p < num).copied();
+            assert_eq!(next_palindrome(num, base), next);
+            assert_eq!(prev_palindrome(num, base), prev);
+        }
+    }
+
+    assert_eq!(next_palindrome(99, 10), Some(101));
+    assert_eq!(next_palindrome(12921, 10), Some(13031));
+    assert_eq!(prev_palindrome(1000, 10), Some(999));
+    assert_eq!(prev_palindrome(0, 10), None);
+    assert_eq!(next_palindrome(u64::MAX - 1, 2), Some(u64::MAX));
+    assert_eq!(next_palindrome(u64::MAX, 10), None);
+    assert_eq!(next_palindrome(18446744066044764480, 10), Some(18446744066044764481));
+    assert_eq!(next_palindrome(18446744066044764481, 10), None);
+    assert_eq!(prev_palindrome(u64::MAX, 10), Some(18446744066044764481));
+}
@@ -0,0 +1,262 @@
+use crate::int::Int;
+
+// Floor of the n-th root, computed with integer Newton iterations
+fn root_u128(num: u128, n: u32) -> u128 {
+    if n == 1 || num < 2 {
+        return num;
+    }
+
+    let bits = 128 - num.leading_zeros();
+    if n >= bits {
+        return 1;
+    }
+
+    // start from a power of two that is not below the root,
+    // the iterations then decrease monotonically to the floor
+    let mut x: u128 = 1 << ((bits + n - 1) / n);
+    loop {
+        let quotient = match x.checked_pow(n - 1) {
+            Some(power) => num / power,
+            None => 0
+        };
+        let next = ((n - 1) as u128 * x + quotient) / n as u128;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+// Odd roots of negative numbers are rounded toward zero,
+// even roots of negative numbers and the zeroth root do not exist.
+pub fn nth_root<T: Int>(num: T, n: u32) -> Option<T> {
+    if n == 0 || (num.is_negative() && n % 2 == 0) {
+        return None;
+    }
+    let root = root_u128(num.magnitude(), n);
+    return Some(T::from_magnitude(root, num.is_negative()));
+}
+
+pub fn isqrt<T: Int>(num: T) -> Option<T> {
+    return nth_root(num, 2);
+}
+
+pub fn icbrt<T: Int>(num: T) -> T {
+    let root = root_u128(num.magnitude(), 3);
+    return T::from_magnitude(root, num.is_negative());
+}
+
+const fn square_residues(modulus: u128) -> u128 {
+    let mut mask: u128 = 0;
+    let mut i: u128 = 0;
+    while i < modulus {
+        mask |= 1 << (i * i % modulus);
+        i += 1;
+    }
+    return mask;
+}
+
+const SQUARES_MOD_64: u128 = square_residues(64);
+const SQUARES_MOD_63: u128 = square_residues(63);
+const SQUARES_MOD_65: u128 = square_residues(65);
+const SQUARES_MOD_11: u128 = square_residues(11);
+
+pub fn is_perfect_square<T: Int>(num: T) -> bool {
+    if num.is_negative() {
+        return false;
+    }
+
+    // cheap filters reject most non-squares before the root is taken
+    let value = num.magnitude();
+    if (SQUARES_MOD_64 >> (value % 64)) & 1 == 0
+        || (SQUARES_MOD_63 >> (value % 63)) & 1 == 0
+        || (SQUARES_MOD_65 >> (value % 65)) & 1 == 0
+        || (SQUARES_MOD_11 >> (value % 11)) & 1 == 0 {
+        return false;
+    }
+
+    let root = root_u128(value, 2);
+    return root * root == value;
+}
+
+// Returns the smallest base and the largest exponent (at least 2)
+// such that base^exponent == num. Values with |num| < 2 are skipped,
+// since they are powers for every exponent.
+pub fn perfect_power<T: Int>(num: T) -> Option<(T, u32)> {
+    let value = num.magnitude();
+    if value < 2 {
+        return None;
+    }
+
+    let bits = 128 - value.leading_zeros();
+    for exponent in (2..bits).rev() {
+        if num.is_negative() && exponent % 2 == 0 {
+            continue;
+        }
+        let root = root_u128(value, exponent);
+        if root >= 2 && root.checked_pow(exponent) == Some(value) {
+            return Some((T::from_magnitude(root, num.is_negative()), exponent));
+        }
+    }
+    return None;
+}
+
+// Returns floor(log_base(num)) and whether num is an exact power of base
+fn ilog_u128(num: u128, base: u128) -> (u32, bool) {
+    let mut exponent = 0;
+    let mut rest = num;
+    let mut exact = true;
+    while rest >= base {
+        exact = exact && rest % base == 0;
+        rest = rest / base;
+        exponent += 1;
+    }
+    return (exponent, exact && rest == 1);
+}
+
+pub fn ilog<T: Int>(num: T, base: T) -> Option<u32> {
+    if num.is_negative() || num.magnitude() == 0
+        || base.is_negative() || base.magnitude() < 2 {
+        return None;
+    }
+    let (exponent, _) = ilog_u128(num.magnitude(), base.magnitude());
+    return Some(exponent);
+}
+
+pub fn ilog_ceil<T: Int>(num: T, base: T) -> Option<u32> {
+    if num.is_negative() || num.magnitude() == 0
+        || base.is_negative() || base.magnitude() < 2 {
+        return None;
+    }
+    let (exponent, exact) = ilog_u128(num.magnitude(), base.magnitude());
+    if exact {
+        return Some(exponent);
+    }
+    return Some(exponent + 1);
+}
+
+// Deterministic sample of large values for the brute force checks
+fn sample_values(count: usize) -> Vec<u64> {
+    let mut state: u64 = 0x2545F4914F6CDD1D;
+    let mut values = vec![];
+    for i in 0..count {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        values.push(state >> (i % 64));
+    }
+    return values;
+}
+
+#[test]
+fn test_roots_small() {
+    for num in 0..=u16::MAX {
+        let sqrt = isqrt(num).unwrap() as u32;
+        assert!(sqrt * sqrt <= num as u32 && (sqrt + 1) * (sqrt + 1) > num as u32);
+
+        let cbrt = icbrt(num) as u32;
+        assert!(cbrt.pow(3) <= num as u32 && (cbrt + 1).pow(3) > num as u32);
+
+        assert_eq!(is_perfect_square(num), sqrt * sqrt == num as u32);
+    }
+
+    for num in i16::MIN..=i16::MAX {
+        let cbrt = icbrt(num) as i32;
+        let value = num as i32;
+        assert!(cbrt.pow(3).abs() <= value.abs() && (cbrt.abs() + 1).pow(3) > value.abs());
+        assert_eq!(cbrt.signum(), value.signum());
+        assert_eq!(isqrt(num).is_none(), num < 0);
+    }
+
+    assert_eq!(isqrt(i8::MAX), Some(11));
+    assert_eq!(icbrt(i8::MIN), -5);
+    assert_eq!(nth_root(-32i8, 5), Some(-2));
+    assert_eq!(nth_root(-16i8, 4), None);
+    assert_eq!(nth_root(16u8, 0), None);
+    assert_eq!(nth_root(200u8, 1), Some(200));
+}
+
+#[test]
+fn test_roots_large() {
+    for num in sample_values(20000) {
+        let wide = num as u128;
+        for n in 2..8 {
+            let root = nth_root(num, n).unwrap() as u128;
+            assert!(root.pow(n) <= wide);
+            assert!((root + 1).pow(n) > wide);
+        }
+    }
+
+    for root in (0..1_000_000u64).chain(u32::MAX as u64 - 1000..=u32::MAX as u64) {
+        let square = root * root;
+        assert_eq!(isqrt(square), Some(root));
+        assert!(is_perfect_square(square));
+        if root > 0 {
+            assert_eq!(isqrt(square - 1), Some(root - 1));
+            assert!(!is_perfect_square(square - 1) || root == 1);
+        }
+        assert!(!is_perfect_square(square + 1) || root == 0);
+    }
+
+    assert_eq!(isqrt(u64::MAX), Some(u32::MAX as u64));
+    assert_eq!(isqrt(u128::MAX), Some(u64::MAX as u128));
+    assert_eq!(icbrt(u128::MAX), 6981463658331);
+    assert_eq!(icbrt(i128::MIN), -5541191377756);
+    assert_eq!(nth_root(u128::MAX, 127), Some(2));
+    assert_eq!(nth_root(u128::MAX, 128), Some(1));
+    assert_eq!(isqrt(i64::MIN), None);
+}
+
+#[test]
+fn test_perfect_power() {
+    assert_eq!(perfect_power(64u32), Some((2, 6)));
+    assert_eq!(perfect_power(-64i32), Some((-4, 3)));
+    assert_eq!(perfect_power(-8i8), Some((-2, 3)));
+    assert_eq!(perfect_power(1000usize), Some((10, 3)));
+    assert_eq!(perfect_power(1u8 << 7), Some((2, 7)));
+    assert_eq!(perfect_power(3u128.pow(80)), Some((3, 80)));
+    assert_eq!(perfect_power(1i64), None);
+    assert_eq!(perfect_power(-4i64), None);
+    assert_eq!(perfect_power(12u16), None);
+
+    for num in 2..20000u32 {
+        let mut expected = None;
+        for base in 2..=isqrt(num).unwrap() {
+            let mut power = base * base;
+            let mut exponent = 2;
+            while power < num {
+                power *= base;
+                exponent += 1;
+            }
+            if power == num {
+                expected = Some((base, exponent));
+                break;
+            }
+        }
+        assert_eq!(perfect_power(num), expected);
+    }
+}
+
+#[test]
+fn test_ilog() {
+    for base in 2..=20u64 {
+        let mut floor = 0;
+        let mut power = 1;
+        for num in 1..50000u64 {
+            if power * base <= num {
+                power *= base;
+                floor += 1;
+            }
+            let ceil = if power == num { floor } else { floor + 1 };
+            assert_eq!(ilog(num, base), Some(floor));
+            assert_eq!(ilog_ceil(num, base), Some(ceil));
+        }
+    }
+
+    assert_eq!(ilog(0u32, 2), None);
+    assert_eq!(ilog(-8i32, 2), None);
+    assert_eq!(ilog(8i32, -2), None);
+    assert_eq!(ilog(8u8, 1), None);
+    assert_eq!(ilog(u128::MAX, 2), Some(127));
+    assert_eq!(ilog_ceil(u128::MAX, 2), Some(128));
+    assert_eq!(ilog(i64::MAX, 10), Some(18));
+    assert_eq!(ilog_ceil(1u8, 10), Some(0));
+}
@@ -1,3 +1,13 @@
+mod int;
+mod roots;
+mod palindrome;
+mod bits;
+mod varint;
+mod crc;
+mod hamming;
+mod const_fns;
+mod digits;
+
 // task 1
 fn bubble_sort(arr: &mut[i32], comp: fn(&i32, &i32)->bool) -> &mut[i32] {
     let len: usize = arr.len();
@@ -27,17 +37,19 @@ fn sort_test() {
 }
 
 // task 2
-fn is_power_of_two(num: i32) -> bool {
+const fn is_power_of_two(num: i32) -> bool {
     if num < 0 {
         return false;
     }
 
     let mut num_ones: i32 = 0;
-    for i in 0..32 {
+    let mut i = 0;
+    while i < 32 {
         num_ones += (num >> i) & 1;
         if num_ones > 1 {
             return false;
         }
+        i += 1;
     }
     return num_ones == 1;
 }
@@ -61,11 +73,13 @@ fn power_test() {
 }
 
 // task 3
-fn reverse_bits(num: u32) -> u32 {
+const fn reverse_bits(num: u32) -> u32 {
     let mut reversed: u32 = 0;
-    for i in 0..32 {
+    let mut i = 0;
+    while i < 32 {
         let bit = (num >> i) & 1;
         reversed = reversed | (bit << 32 - i - 1);
+        i += 1;
     }
     return reversed;
 }
@@ -81,7 +95,7 @@ fn reverse_test() {
 }
 
 // task 4
-fn digit_sum(num: i32) -> i32 {
+const fn digit_sum(num: i32) -> i32 {
     let mut total = 0;
     let mut divided = num;
     while divided > 0 {
@@ -91,7 +105,7 @@ fn digit_sum(num: i32) -> i32 {
     return total;
 }
 
-fn add_digits(num: i32) -> i32 {
+const fn add_digits(num: i32) -> i32 {
     let mut result = num;
     while result > 9 {
         result = digit_sum(result)
@@ -99,7 +113,7 @@ fn add_digits(num: i32) -> i32 {
     return result;
 }
 
-fn add_digits_simple(num: i32) -> i32 {
+const fn add_digits_simple(num: i32) -> i32 {
     if num == 0 {
         return 0;
     }
@@ -136,7 +150,7 @@ fn sum_test() {
 
 
 // task 5
-fn find_palindrome(num: i32) -> bool {
+const fn find_palindrome(num: i32) -> bool {
     if num < 0 {
         return false;
     }
@@ -164,5 +178,22 @@ fn palindrome_test() {
     assert_eq!(find_palindrome(44), true);
 }
 
+#[test]
+fn const_test() {
+    const POWER: bool = is_power_of_two(1 << 20);
+    const REVERSED: u32 = reverse_bits(43261596);
+    const ROOT: i32 = add_digits(123456789);
+    const ROOT_SIMPLE: i32 = add_digits_simple(123456789);
+    const PALINDROME: bool = find_palindrome(123321);
+    const BUFFER: [u8; digit_sum(4444) as usize] = [0; digit_sum(4444) as usize];
+
+    assert_eq!(POWER, true);
+    assert_eq!(REVERSED, 964176192);
+    assert_eq!(ROOT, 9);
+    assert_eq!(ROOT_SIMPLE, 9);
+    assert_eq!(PALINDROME, true);
+    assert_eq!(BUFFER.len(), 16);
+}
+
 fn main() {
 }
\ No newline at end of file
@@ -0,0 +1,340 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, ErrorKind, Read, Write};
+use crate::int::Int;
+
+// Unsigned types are written as unsigned LEB128, signed types as signed LEB128,
+// signed values can be mapped to unsigned ones with zigzag encoding first.
+
+const MAX_BYTES: usize = 19; // 128 bits in groups of 7
+
+#[derive(Debug)]
+pub enum VarIntError {
+    Truncated,
+    Overlong,
+    Overflow,
+    BufferTooSmall,
+    Io(io::Error)
+}
+
+impl Display for VarIntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            VarIntError::Truncated => write!(f, "input ended inside a variable length integer"),
+            VarIntError::Overlong => write!(f, "variable length integer has redundant bytes"),
+            VarIntError::Overflow => write!(f, "variable length integer does not fit the target type"),
+            VarIntError::BufferTooSmall => write!(f, "buffer is too small for the encoded integer"),
+            VarIntError::Io(error) => write!(f, "{}", error)
+        };
+    }
+}
+
+impl std::error::Error for VarIntError {}
+
+impl From<io::Error> for VarIntError {
+    fn from(error: io::Error) -> Self {
+        return VarIntError::Io(error);
+    }
+}
+
+fn encode_raw<T: Int>(value: T) -> ([u8; MAX_BYTES], usize) {
+    let mut bytes = [0u8; MAX_BYTES];
+    let mut len = 0;
+
+    if T::SIGNED {
+        // sign extend the raw bits to the full width
+        let mut rest = (value.to_bits() << (128 - T::BITS)) as i128 >> (128 - T::BITS);
+        loop {
+            let byte = (rest & 0x7f) as u8;
+            rest >>= 7;
+            let done = (rest == 0 && byte & 0x40 == 0) || (rest == -1 && byte & 0x40 != 0);
+            bytes[len] = if done { byte } else { byte | 0x80 };
+            len += 1;
+            if done {
+                break;
+            }
+        }
+    } else {
+        let mut rest = value.to_bits();
+        loop {
+            let byte = (rest & 0x7f) as u8;
+            rest >>= 7;
+            bytes[len] = if rest == 0 { byte } else { byte | 0x80 };
+            len += 1;
+            if rest == 0 {
+                break;
+            }
+        }
+    }
+    return (bytes, len);
+}
+
+fn decode_with<T: Int, F>(mut next_byte: F) -> Result<(T, usize), VarIntError>
+    where F: FnMut() -> Result<Option<u8>, VarIntError>
+{
+    let max_len = ((T::BITS + 6) / 7) as usize;
+    let mut result: u128 = 0;
+    let mut shift = 0;
+    let mut len = 0;
+    let mut prev: u8 = 0;
+
+    loop {
+        let byte = match next_byte()? {
+            Some(byte) => byte,
+            None => return Err(VarIntError::Truncated)
+        };
+        len += 1;
+        if len > max_len {
+            return Err(VarIntError::Overflow);
+        }
+
+        let payload = byte & 0x7f;
+        if shift + 7 > T::BITS && byte & 0x80 == 0 {
+            // the last byte may only carry the remaining bits of the type,
+            // for signed types the bits above them repeat the sign bit
+            let used = T::BITS - shift;
+            let fits = if T::SIGNED {
+                let upper = payload >> (used - 1);
+                upper == 0 || upper == 0x7f >> (used - 1)
+            } else {
+                payload >> used == 0
+            };
+            if !fits {
+                return Err(VarIntError::Overflow);
+            }
+        }
+        result |= (payload as u128) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            if len > 1 {
+                let redundant = if T::SIGNED {
+                    (byte == 0 && prev & 0x40 == 0) || (byte == 0x7f && prev & 0x40 != 0)
+                } else {
+                    byte == 0
+                };
+                if redundant {
+                    return Err(VarIntError::Overlong);
+                }
+            }
+            if T::SIGNED && byte & 0x40 != 0 && shift < 128 {
+                result |= u128::MAX << shift;
+            }
+            return Ok((T::from_bits(result), len));
+        }
+        prev = byte;
+    }
+}
+
+pub fn encode<T: Int>(value: T, out: &mut Vec<u8>) -> usize {
+    let (bytes, len) = encode_raw(value);
+    out.extend_from_slice(&bytes[..len]);
+    return len;
+}
+
+pub fn encode_slice<T: Int>(value: T, buf: &mut [u8]) -> Result<usize, VarIntError> {
+    let (bytes, len) = encode_raw(value);
+    if buf.len() < len {
+        return Err(VarIntError::BufferTooSmall);
+    }
+    buf[..len].copy_from_slice(&bytes[..len]);
+    return Ok(len);
+}
+
+// Returns the value and the number of bytes it took
+pub fn decode<T: Int>(buf: &[u8]) -> Result<(T, usize), VarIntError> {
+    let mut bytes = buf.iter();
+    return decode_with(|| Ok(bytes.next().copied()));
+}
+
+pub fn write<T: Int, W: Write>(writer: &mut W, value: T) -> io::Result<usize> {
+    let (bytes, len) = encode_raw(value);
+    writer.write_all(&bytes[..len])?;
+    return Ok(len);
+}
+
+pub fn read<T: Int, R: Read>(reader: &mut R) -> Result<T, VarIntError> {
+    let (value, _) = decode_with(|| {
+        let mut byte = [0u8];
+        match reader.read_exact(&mut byte) {
+            Ok(()) => Ok(Some(byte[0])),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(VarIntError::Io(error))
+        }
+    })?;
+    return Ok(value);
+}
+
+// ----------- ZIGZAG ------------
+
+// Interleaves negative and positive values: 0, -1, 1, -2, 2, ...
+pub trait ZigZag: Int {
+    type Unsigned: Int;
+
+    fn zigzag(self) -> Self::Unsigned;
+    fn unzigzag(value: Self::Unsigned) -> Self;
+}
+
+macro_rules! impl_zigzag {
+    ($($t:ty => $u:ty),*) => {$(
+        impl ZigZag for $t {
+            type Unsigned = $u;
+
+            fn zigzag(self) -> $u {
+                return ((self << 1) ^ (self >> (<$t>::BITS - 1))) as $u;
+            }
+
+            fn unzigzag(value: $u) -> $t {
+                return ((value >> 1) as $t) ^ -((value & 1) as $t);
+            }
+        }
+    )*};
+}
+
+impl_zigzag!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+pub fn zigzag_encode<T: ZigZag>(value: T) -> T::Unsigned {
+    return value.zigzag();
+}
+
+pub fn zigzag_decode<T: ZigZag>(value: T::Unsigned) -> T {
+    return T::unzigzag(value);
+}
+
+fn encoded<T: Int>(value: T) -> Vec<u8> {
+    let mut out = vec![];
+    encode(value, &mut out);
+    return out;
+}
+
+fn check_round_trip<T: Int + std::fmt::Debug>(value: T) {
+    let bytes = encoded(value);
+    let (decoded, len) = decode::<T>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(len, bytes.len());
+    assert!(matches!(decode::<T>(&bytes[..len - 1]), Err(VarIntError::Truncated)));
+}
+
+#[test]
+fn test_known_encodings() {
+    assert_eq!(encoded(0u32), [0x00]);
+    assert_eq!(encoded(127u8), [0x7f]);
+    assert_eq!(encoded(128u16), [0x80, 0x01]);
+    assert_eq!(encoded(624485u32), [0xe5, 0x8e, 0x26]);
+    assert_eq!(encoded(u64::MAX), [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+    assert_eq!(encoded(u128::MAX).len(), 19);
+
+    assert_eq!(encoded(0i32), [0x00]);
+    assert_eq!(encoded(63i8), [0x3f]);
+    assert_eq!(encoded(64i16), [0xc0, 0x00]);
+    assert_eq!(encoded(-1i64), [0x7f]);
+    assert_eq!(encoded(-64i32), [0x40]);
+    assert_eq!(encoded(-65i32), [0xbf, 0x7f]);
+    assert_eq!(encoded(-123456i32), [0xc0, 0xbb, 0x78]);
+    assert_eq!(encoded(i8::MIN), [0x80, 0x7f]);
+    assert_eq!(encoded(i128::MIN).len(), 19);
+
+    let mut buf = [0u8; 2];
+    assert_eq!(encode_slice(300u16, &mut buf).unwrap(), 2);
+    assert_eq!(buf, [0xac, 0x02]);
+    assert!(matches!(encode_slice(1u32 << 14, &mut buf), Err(VarIntError::BufferTooSmall)));
+}
+
+#[test]
+fn test_round_trips() {
+    for value in u8::MIN..=u8::MAX {
+        check_round_trip(value);
+    }
+    for value in i16::MIN..=i16::MAX {
+        check_round_trip(value);
+        check_round_trip(value as u16);
+    }
+
+    let mut state: u64 = 0x853C49E6748FEA9B;
+    for i in 0..2000 {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let value = state >> (i % 64);
+        check_round_trip(value as u32);
+        check_round_trip(value as i32);
+        check_round_trip(value);
+        check_round_trip(value as i64);
+        check_round_trip(value as usize);
+        check_round_trip(value as isize);
+        check_round_trip((value as u128) << (i % 64) | value as u128);
+        check_round_trip(-(value as i128) << (i % 64));
+    }
+    for value in [i64::MIN, i64::MAX, -1, 0, 1] {
+        check_round_trip(value);
+    }
+    for value in [i128::MIN, i128::MAX] {
+        check_round_trip(value);
+    }
+    check_round_trip(u128::MAX);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert!(matches!(decode::<u32>(&[]), Err(VarIntError::Truncated)));
+    assert!(matches!(decode::<u32>(&[0x80]), Err(VarIntError::Truncated)));
+    assert!(matches!(decode::<i32>(&[0xff, 0xff]), Err(VarIntError::Truncated)));
+
+    assert!(matches!(decode::<u32>(&[0x80, 0x00]), Err(VarIntError::Overlong)));
+    assert!(matches!(decode::<u32>(&[0x81, 0x80, 0x00]), Err(VarIntError::Overlong)));
+    assert!(matches!(decode::<i32>(&[0xff, 0x7f]), Err(VarIntError::Overlong)));
+    assert!(matches!(decode::<i32>(&[0x80, 0x00]), Err(VarIntError::Overlong)));
+    assert!(matches!(decode::<i32>(&[0xc0, 0x00]), Ok((64, 2))));
+    assert!(matches!(decode::<i32>(&[0xbf, 0x7f]), Ok((-65, 2))));
+
+    assert!(matches!(decode::<u8>(&[0xff, 0x01]), Ok((255, 2))));
+    assert!(matches!(decode::<u8>(&[0x80, 0x02]), Err(VarIntError::Overflow)));
+    assert!(matches!(decode::<u8>(&[0x80, 0x80, 0x01]), Err(VarIntError::Overflow)));
+    assert!(matches!(decode::<i8>(&[0x80, 0x01]), Err(VarIntError::Overflow)));
+    assert!(matches!(decode::<i8>(&[0xff, 0x7e]), Err(VarIntError::Overflow)));
+    assert!(matches!(decode::<i8>(&[0x80, 0x7f]), Ok((-128, 2))));
+    assert!(matches!(decode::<u64>(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]),
+                     Err(VarIntError::Overflow)));
+
+    // trailing bytes are left for the caller
+    assert!(matches!(decode::<u16>(&[0x05, 0x80]), Ok((5, 1))));
+}
+
+#[test]
+fn test_streams() {
+    let mut out: Vec<u8> = vec![];
+    assert_eq!(write(&mut out, 624485u32).unwrap(), 3);
+    assert_eq!(write(&mut out, -2i64).unwrap(), 1);
+    assert_eq!(write(&mut out, zigzag_encode(-300i16)).unwrap(), 2);
+    assert_eq!(write(&mut out, 1u64 << 40).unwrap(), 6);
+
+    let mut reader = io::Cursor::new(out);
+    assert_eq!(read::<u32, _>(&mut reader).unwrap(), 624485);
+    assert_eq!(read::<i64, _>(&mut reader).unwrap(), -2);
+    assert_eq!(zigzag_decode::<i16>(read(&mut reader).unwrap()), -300);
+    assert_eq!(read::<u64, _>(&mut reader).unwrap(), 1 << 40);
+    assert!(matches!(read::<u8, _>(&mut reader), Err(VarIntError::Truncated)));
+
+    let mut truncated = io::Cursor::new(vec![0xe5, 0x8e]);
+    let error = read::<u32, _>(&mut truncated).unwrap_err();
+    assert_eq!(error.to_string(), "input ended inside a variable length integer");
+}
+
+#[test]
+fn test_zigzag() {
+    assert_eq!(zigzag_encode(0i32), 0);
+    assert_eq!(zigzag_encode(-1i32), 1);
+    assert_eq!(zigzag_encode(1i32), 2);
+    assert_eq!(zigzag_encode(-2i32), 3);
+    assert_eq!(zigzag_encode(i32::MAX), u32::MAX - 1);
+    assert_eq!(zigzag_encode(i32::MIN), u32::MAX);
+    assert_eq!(zigzag_encode(i128::MIN), u128::MAX);
+    assert_eq!(encoded(zigzag_encode(-64i64)), [0x7f]);
+
+    for value in i16::MIN..=i16::MAX {
+        assert_eq!(zigzag_decode::<i16>(zigzag_encode(value)), value);
+        assert_eq!(zigzag_encode(value) as u32, zigzag_encode(value as i32));
+    }
+    for value in [i8::MIN, -1, 0, 1, i8::MAX] {
+        assert_eq!(zigzag_decode::<i8>(zigzag_encode(value)), value);
+    }
+    assert_eq!(zigzag_decode::<i64>(u64::MAX), i64::MIN);
+    assert_eq!(zigzag_decode::<isize>(4), 2);
+}
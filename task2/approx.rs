@@ -0,0 +1,192 @@
+// Approximate equality for floating point values and the types built from
+// them, shared by the task2 crates: #[macro_use] mod approx;
+#![allow(dead_code, unused_macros)]
+
+use std::fmt::{Display, Formatter};
+
+// How far apart two values are. Types with several components report the
+// largest difference of any component, each measure on its own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difference {
+    pub abs: f64,
+    // relative to the larger magnitude of the two
+    pub rel: f64,
+    // representable values in between, in the precision of the component
+    pub ulps: u64
+}
+
+impl Difference {
+    pub const ZERO: Difference = Difference { abs: 0.0, rel: 0.0, ulps: 0 };
+    // NaNs and values that cannot be compared, e.g. matrices of different shapes
+    pub const INFINITE: Difference = Difference { abs: f64::INFINITY, rel: f64::INFINITY, ulps: u64::MAX };
+
+    pub fn max(self, other: Difference) -> Difference {
+        return Difference {
+            abs: if other.abs > self.abs || other.abs.is_nan() { other.abs } else { self.abs },
+            rel: if other.rel > self.rel || other.rel.is_nan() { other.rel } else { self.rel },
+            ulps: self.ulps.max(other.ulps)
+        };
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "abs {:e}, rel {:e}, {} ulps", self.abs, self.rel, self.ulps);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    Absolute(f64),
+    Relative(f64),
+    Ulps(u64)
+}
+
+impl Tolerance {
+    pub fn accepts(&self, difference: &Difference) -> bool {
+        return match *self {
+            Tolerance::Absolute(limit) => difference.abs <= limit,
+            Tolerance::Relative(limit) => difference.rel <= limit,
+            Tolerance::Ulps(limit) => difference.ulps <= limit
+        };
+    }
+}
+
+impl Display for Tolerance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Tolerance::Absolute(limit) => write!(f, "abs {:e}", limit),
+            Tolerance::Relative(limit) => write!(f, "rel {:e}", limit),
+            Tolerance::Ulps(limit) => write!(f, "{} ulps", limit)
+        };
+    }
+}
+
+pub trait ApproxEq {
+    fn difference(&self, other: &Self) -> Difference;
+
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        return tolerance.accepts(&self.difference(other));
+    }
+}
+
+// The bits of a float read as sign and magnitude, turned into an integer
+// that counts the representable values up from the most negative one.
+// -0.0 and 0.0 become the same number.
+macro_rules! impl_float_approx {
+    ($($t:ty => $bits:ty),*) => {$(
+        impl ApproxEq for $t {
+            fn difference(&self, other: &$t) -> Difference {
+                if self == other {
+                    return Difference::ZERO;
+                }
+                if self.is_nan() || other.is_nan() || self.is_infinite() || other.is_infinite() {
+                    return Difference::INFINITE;
+                }
+
+                let ordered = |value: $t| {
+                    let bits = value.to_bits() as $bits as i128;
+                    return if bits < 0 { <$bits>::MIN as i128 - bits } else { bits };
+                };
+                let abs = (*self as f64 - *other as f64).abs();
+                return Difference {
+                    abs,
+                    rel: abs / (*self as f64).abs().max((*other as f64).abs()),
+                    ulps: (ordered(*self) - ordered(*other)).unsigned_abs() as u64
+                };
+            }
+        }
+    )*};
+}
+
+impl_float_approx!(f32 => i32, f64 => i64);
+
+// assert_approx_eq!(a, b) allows 4 ulps, other tolerances are given as
+// abs = 1e-9, rel = 1e-6 or ulps = 16. A message can follow like for assert_eq!.
+macro_rules! assert_approx_eq {
+    (@check $left:expr, $right:expr, $tolerance:expr, $expected:expr $(, $($message:tt)+)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let tolerance = $tolerance;
+                let difference = $crate::approx::ApproxEq::difference(left, right);
+                if tolerance.accepts(&difference) != $expected {
+                    let relation = if $expected { "≈" } else { "≉" };
+                    let message: Option<String> = None $(.or(Some(format!($($message)+))))?;
+                    panic!("assertion `left {} right` failed ({}){}\n  left: {:?}\n right: {:?}\n  diff: {}",
+                           relation, tolerance, message.map(|text| format!(": {}", text)).unwrap_or_default(),
+                           left, right, difference);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr $(,)?) => {
+        assert_approx_eq!($left, $right, ulps = 4)
+    };
+    ($left:expr, $right:expr, abs = $limit:expr $(, $($message:tt)+)?) => {
+        assert_approx_eq!(@check $left, $right, $crate::approx::Tolerance::Absolute($limit), true $(, $($message)+)?)
+    };
+    ($left:expr, $right:expr, rel = $limit:expr $(, $($message:tt)+)?) => {
+        assert_approx_eq!(@check $left, $right, $crate::approx::Tolerance::Relative($limit), true $(, $($message)+)?)
+    };
+    ($left:expr, $right:expr, ulps = $limit:expr $(, $($message:tt)+)?) => {
+        assert_approx_eq!(@check $left, $right, $crate::approx::Tolerance::Ulps($limit), true $(, $($message)+)?)
+    };
+}
+
+// The opposite of assert_approx_eq!, with the same tolerances
+macro_rules! assert_approx_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        assert_approx_ne!($left, $right, ulps = 4)
+    };
+    ($left:expr, $right:expr, abs = $limit:expr $(, $($message:tt)+)?) => {
+        assert_approx_eq!(@check $left, $right, $crate::approx::Tolerance::Absolute($limit), false $(, $($message)+)?)
+    };
+    ($left:expr, $right:expr, rel = $limit:expr $(, $($message:tt)+)?) => {
+        assert_approx_eq!(@check $left, $right, $crate::approx::Tolerance::Relative($limit), false $(, $($message)+)?)
+    };
+    ($left:expr, $right:expr, ulps = $limit:expr $(, $($message:tt)+)?) => {
+        assert_approx_eq!(@check $left, $right, $crate::approx::Tolerance::Ulps($limit), false $(, $($message)+)?)
+    };
+}
+
+#[test]
+fn test_float_difference() {
+    assert_eq!(1.0f64.difference(&1.0), Difference::ZERO);
+    assert_eq!(0.0f64.difference(&-0.0), Difference::ZERO);
+    assert_eq!(f64::INFINITY.difference(&f64::INFINITY), Difference::ZERO);
+    assert_eq!(f64::NAN.difference(&f64::NAN), Difference::INFINITE);
+    assert_eq!(f32::MAX.difference(&f32::INFINITY), Difference::INFINITE);
+
+    let next = f64::from_bits(1.0f64.to_bits() + 1);
+    assert_eq!(1.0f64.difference(&next), Difference { abs: f64::EPSILON, rel: f64::EPSILON / next, ulps: 1 });
+    assert_eq!(1.0f32.difference(&1.5).ulps, 1 << 22);
+    // the smallest subnormals on both sides of zero are two steps apart
+    assert_eq!(f64::from_bits(1).difference(&-f64::from_bits(1)).ulps, 2);
+    assert_eq!((-1.0f32).difference(&-1.0000001).ulps, 1);
+
+    assert_eq!(2.0f64.difference(&-2.0), Difference { abs: 4.0, rel: 2.0, ulps: 2 * 2.0f64.to_bits() });
+    assert_eq!(Difference { abs: 1.0, rel: 0.5, ulps: 3 }.max(Difference { abs: 0.5, rel: 1.0, ulps: 7 }),
+               Difference { abs: 1.0, rel: 1.0, ulps: 7 });
+
+    assert!(0.1f64.approx_eq(&0.10000001, Tolerance::Relative(1e-6)));
+    assert!(!0.1f64.approx_eq(&0.10000001, Tolerance::Ulps(1000)));
+    assert!(1e-20f64.approx_eq(&0.0, Tolerance::Absolute(1e-15)));
+    assert!(!1e-20f64.approx_eq(&0.0, Tolerance::Relative(0.5)));
+}
+
+#[test]
+fn test_assert_macros() {
+    assert_approx_eq!(0.1 + 0.2, 0.3);
+    assert_approx_eq!(0.1f32 + 0.2, 0.3);
+    assert_approx_eq!(std::f64::consts::PI.sin(), 0.0, abs = 1e-15);
+    assert_approx_eq!(1e6 + 1.0, 1e6, rel = 1e-5, "{} digits", 5);
+    assert_approx_eq!(1.0, 1.0 + 8.0 * f64::EPSILON, ulps = 8);
+    assert_approx_ne!(1.0, 1.001);
+    assert_approx_ne!(1.0, 1.001, rel = 1e-4);
+    assert_approx_ne!(f64::NAN, f64::NAN, abs = 1e300);
+
+    let result = std::panic::catch_unwind(|| assert_approx_eq!(1.0, 1.5, abs = 0.1, "step {}", 3));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(message, "assertion `left ≈ right` failed (abs 1e-1): step 3\n  left: 1.0\n right: 1.5\n  \
+                         diff: abs 5e-1, rel 3.333333333333333e-1, 2251799813685248 ulps");
+}
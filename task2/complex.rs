@@ -0,0 +1,810 @@
+// Complex numbers over any component type, shared by the task2 crates that
+// need them: mod num; mod approx; mod complex; and the prng module
+#![allow(dead_code)]
+
+use std::fmt::{Debug, Display, Formatter, LowerExp, UpperExp};
+use std::iter::{Sum, Product};
+use std::str::FromStr;
+use std::ops::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign, Neg, Range};
+use crate::prng::{Rng, SampleUniform};
+use crate::num::{Zero, One, Num, Signed, Float};
+use crate::approx::{ApproxEq, Difference};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComplexError {
+    DivisionByZero,
+    // the quotient, or a product on the way, does not fit the component type
+    Overflow
+}
+
+impl Display for ComplexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ComplexError::DivisionByZero => write!(f, "complex division by zero"),
+            ComplexError::Overflow => write!(f, "complex division overflow")
+        };
+    }
+}
+
+impl std::error::Error for ComplexError {}
+
+// ----------- DIVISION ------------
+
+// Division differs between integers and floats, so every component type
+// provides its own. The operators and checked_div all go through this.
+pub trait ComplexDiv: Sized {
+    fn checked_div(lhs: &Complex<Self>, rhs: &Complex<Self>) -> Result<Complex<Self>, ComplexError>;
+}
+
+// (a + bi) / (c + di) = ((ac + bd) + (bc - ad)i) / (c² + d²). Signed components
+// are gaussian integers, both parts are rounded to the nearest integer (halves
+// up), which makes the remainder a - qb the smallest possible: N(r) <= N(b) / 2.
+// The products are taken in i128, which only i128 components can overflow;
+// that, and a quotient that does not fit the component type, is an Overflow.
+macro_rules! impl_gaussian_div {
+    ($($t:ty),*) => {$(
+        impl ComplexDiv for $t {
+            fn checked_div(lhs: &Complex<$t>, rhs: &Complex<$t>) -> Result<Complex<$t>, ComplexError> {
+                let (a, b, c, d) = (lhs.re as i128, lhs.im as i128, rhs.re as i128, rhs.im as i128);
+                let sum = |x: i128, y: i128, z: i128, w: i128, sign: i128| {
+                    return x.checked_mul(y)
+                        .zip(z.checked_mul(w).and_then(|product| product.checked_mul(sign)))
+                        .and_then(|(left, right)| left.checked_add(right))
+                        .ok_or(ComplexError::Overflow);
+                };
+                let square = sum(c, c, d, d, 1)?;
+                if square == 0 {
+                    return Err(ComplexError::DivisionByZero);
+                }
+                let nearest = |numerator: i128| {
+                    let quotient = numerator.div_euclid(square);
+                    let remainder = numerator.rem_euclid(square);
+                    let rounded = if remainder >= square - remainder { quotient.checked_add(1) } else { Some(quotient) };
+                    return rounded
+                        .and_then(|value| <$t>::try_from(value).ok())
+                        .ok_or(ComplexError::Overflow);
+                };
+                return Ok(Complex {
+                    re: nearest(sum(a, c, b, d, 1)?)?,
+                    im: nearest(sum(b, c, a, d, -1)?)?
+                });
+            }
+        }
+    )*};
+}
+
+// Unsigned components can not hold the conjugate, the parts are truncated.
+// A negative imaginary part of at least one is not representable, it and
+// products that do not fit the type are reported as Overflow.
+macro_rules! impl_int_div {
+    ($($t:ty),*) => {$(
+        impl ComplexDiv for $t {
+            fn checked_div(lhs: &Complex<$t>, rhs: &Complex<$t>) -> Result<Complex<$t>, ComplexError> {
+                let product = |x: $t, y: $t, z: $t, w: $t| {
+                    return x.checked_mul(y)
+                        .zip(z.checked_mul(w))
+                        .ok_or(ComplexError::Overflow);
+                };
+                let (cc, dd) = product(rhs.re, rhs.re, rhs.im, rhs.im)?;
+                let square = cc.checked_add(dd).ok_or(ComplexError::Overflow)?;
+                if square == 0 {
+                    return Err(ComplexError::DivisionByZero);
+                }
+                let (ac, bd) = product(lhs.re, rhs.re, lhs.im, rhs.im)?;
+                let (bc, ad) = product(lhs.im, rhs.re, lhs.re, rhs.im)?;
+                let re = ac.checked_add(bd).ok_or(ComplexError::Overflow)? / square;
+                let im = match bc.checked_sub(ad) {
+                    Some(numerator) => numerator / square,
+                    None if ad - bc < square => 0,
+                    None => return Err(ComplexError::Overflow)
+                };
+                return Ok(Complex { re, im });
+            }
+        }
+    )*};
+}
+
+// Smith's algorithm divides by the larger divisor part first, so c² + d²
+// is never formed and can not overflow or underflow. Floats and fixed-point
+// numbers both use it.
+pub fn smith_div<T: Signed>(lhs: &Complex<T>, rhs: &Complex<T>) -> Result<Complex<T>, ComplexError> {
+    if rhs.re.is_zero() && rhs.im.is_zero() {
+        return Err(ComplexError::DivisionByZero);
+    }
+    let (a, b, c, d) = (lhs.re, lhs.im, rhs.re, rhs.im);
+    if c.abs() >= d.abs() {
+        let ratio = d / c;
+        let denominator = c + d * ratio;
+        return Ok(Complex {
+            re: (a + b * ratio) / denominator,
+            im: (b - a * ratio) / denominator
+        });
+    }
+    let ratio = c / d;
+    let denominator = c * ratio + d;
+    return Ok(Complex {
+        re: (a * ratio + b) / denominator,
+        im: (b * ratio - a) / denominator
+    });
+}
+
+impl<T: Float> ComplexDiv for T {
+    fn checked_div(lhs: &Complex<T>, rhs: &Complex<T>) -> Result<Complex<T>, ComplexError> {
+        return smith_div(lhs, rhs);
+    }
+}
+
+impl_gaussian_div!(i8, i16, i32, i64, i128, isize);
+impl_int_div!(u8, u16, u32, u64, u128, usize);
+
+impl<T: ComplexDiv> Complex<T> {
+    pub fn checked_div(&self, other: &Complex<T>) -> Result<Complex<T>, ComplexError> {
+        return T::checked_div(self, other);
+    }
+}
+
+// ----------- FUNCTIONS ------------
+
+impl<T: Neg<Output = T> + Copy> Complex<T> {
+    pub fn conj(&self) -> Complex<T> {
+        return Complex {
+            re: self.re,
+            im: -self.im
+        };
+    }
+}
+
+// Branch cuts follow C99 and most libraries: ln, sqrt, powf and powc are cut
+// along the negative real axis, which belongs to the upper half plane when
+// the imaginary part is +0 and to the lower one when it is -0. arg is in (-π, π].
+impl<T: Float> Complex<T> {
+    pub fn from_polar(r: T, theta: T) -> Complex<T> {
+        return Complex {
+            re: r * theta.cos(),
+            im: r * theta.sin()
+        };
+    }
+
+    pub fn to_polar(&self) -> (T, T) {
+        return (self.norm(), self.arg());
+    }
+
+    pub fn norm(&self) -> T {
+        return self.re.hypot(self.im);
+    }
+
+    pub fn norm_sqr(&self) -> T {
+        return self.re * self.re + self.im * self.im;
+    }
+
+    pub fn arg(&self) -> T {
+        return self.im.atan2(self.re);
+    }
+
+    pub fn exp(&self) -> Complex<T> {
+        return Self::from_polar(self.re.exp(), self.im);
+    }
+
+    pub fn ln(&self) -> Complex<T> {
+        return Complex {
+            re: self.norm().ln(),
+            im: self.arg()
+        };
+    }
+
+    // the root with a non-negative real part
+    pub fn sqrt(&self) -> Complex<T> {
+        if self.re.is_zero() && self.im.is_zero() {
+            return Complex { re: T::zero(), im: self.im };
+        }
+        let t = ((self.re.abs() + self.norm()) / T::two()).sqrt();
+        if !self.re.is_negative() {
+            return Complex { re: t, im: self.im / (T::two() * t) };
+        }
+        return Complex { re: self.im.abs() / (T::two() * t), im: t.copysign(self.im) };
+    }
+
+    pub fn powf(&self, exponent: T) -> Complex<T> {
+        let (r, theta) = self.to_polar();
+        return Self::from_polar(r.powf(exponent), theta * exponent);
+    }
+
+    // 0 to the power w is 1 for w = 0 and 0 when w has a positive real part
+    pub fn powc(&self, exponent: Complex<T>) -> Complex<T> {
+        if self.re.is_zero() && self.im.is_zero() {
+            if exponent.re.is_zero() && exponent.im.is_zero() {
+                return Complex { re: T::one(), im: T::zero() };
+            }
+            if exponent.re > T::zero() {
+                return Complex { re: T::zero(), im: T::zero() };
+            }
+            return Complex { re: T::nan(), im: T::nan() };
+        }
+        return (exponent * self.ln()).exp();
+    }
+
+    pub fn sin(&self) -> Complex<T> {
+        return Complex {
+            re: self.re.sin() * self.im.cosh(),
+            im: self.re.cos() * self.im.sinh()
+        };
+    }
+
+    pub fn cos(&self) -> Complex<T> {
+        return Complex {
+            re: self.re.cos() * self.im.cosh(),
+            im: -self.re.sin() * self.im.sinh()
+        };
+    }
+
+    // tan z = (sin 2a + i sinh 2b) / (cos 2a + cosh 2b), far from the real
+    // axis cosh overflows while the value is already ±i within precision
+    pub fn tan(&self) -> Complex<T> {
+        if self.im.abs() > T::from_f64(20.0) {
+            return Complex { re: T::zero(), im: T::one().copysign(self.im) };
+        }
+        let (a, b) = (T::two() * self.re, T::two() * self.im);
+        let denominator = a.cos() + b.cosh();
+        return Complex {
+            re: a.sin() / denominator,
+            im: b.sinh() / denominator
+        };
+    }
+
+    pub fn sinh(&self) -> Complex<T> {
+        return Complex {
+            re: self.re.sinh() * self.im.cos(),
+            im: self.re.cosh() * self.im.sin()
+        };
+    }
+
+    pub fn cosh(&self) -> Complex<T> {
+        return Complex {
+            re: self.re.cosh() * self.im.cos(),
+            im: self.re.sinh() * self.im.sin()
+        };
+    }
+
+    // tanh z = -i tan(iz)
+    pub fn tanh(&self) -> Complex<T> {
+        let tan = Complex { re: -self.im, im: self.re }.tan();
+        return Complex { re: tan.im, im: -tan.re };
+    }
+}
+
+// ----------- SCALAR ------------
+
+// Field elements the linear algebra and polynomial code works with.
+// magnitude is only used to compare sizes, e.g. to pick pivots.
+pub trait Scalar: Num + Neg<Output = Self> + Debug + Display {
+    fn conj(self) -> Self;
+    fn magnitude(self) -> f64;
+}
+
+impl<T: Float + Debug + Display> Scalar for T {
+    fn conj(self) -> T {
+        return self;
+    }
+
+    fn magnitude(self) -> f64 {
+        return self.abs().to_f64();
+    }
+}
+
+impl<T: Float + Debug + Display> Scalar for Complex<T> {
+    fn conj(self) -> Complex<T> {
+        return Complex { re: self.re, im: -self.im };
+    }
+
+    fn magnitude(self) -> f64 {
+        return self.norm().to_f64();
+    }
+}
+
+impl<T: Num> Zero for Complex<T> {
+    fn zero() -> Complex<T> {
+        return Complex { re: T::zero(), im: T::zero() };
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.re.is_zero() && self.im.is_zero();
+    }
+}
+
+impl<T: Num> One for Complex<T> {
+    fn one() -> Complex<T> {
+        return Complex { re: T::one(), im: T::zero() };
+    }
+}
+
+// the parts are compared on their own, a tiny imaginary part next to a
+// large real one is not hidden by the norm
+impl<T: ApproxEq> ApproxEq for Complex<T> {
+    fn difference(&self, other: &Complex<T>) -> Difference {
+        return self.re.difference(&other.re).max(self.im.difference(&other.im));
+    }
+}
+
+impl<T: SampleUniform + Copy> Complex<T> {
+    // both parts are drawn independently from the same range
+    pub fn random<R: Rng>(rng: &mut R, range: Range<T>) -> Complex<T> {
+        return Complex {
+            re: rng.range(range.start..range.end),
+            im: rng.range(range.start..range.end)
+        };
+    }
+}
+
+impl<T: Add<Output = T>> Add for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, other: Self) -> Self::Output {
+        return Complex {
+            re: self.re + other.re,
+            im: self.im + other.im
+        };
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        return Complex {
+            re: self.re - other.re,
+            im: self.im - other.im
+        };
+    }
+}
+
+impl<T: Num> Mul for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        return Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re
+        };
+    }
+}
+
+// The operators panic on a zero divisor, use checked_div to handle it
+fn divide<T: ComplexDiv>(lhs: &Complex<T>, rhs: &Complex<T>) -> Complex<T> {
+    return match T::checked_div(lhs, rhs) {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error)
+    };
+}
+
+impl<T: ComplexDiv> Div for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, other: Self) -> Self::Output {
+        return divide(&self, &other);
+    }
+}
+
+impl<T: AddAssign<T>> AddAssign for Complex<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.re += other.re;
+        self.im += other.im;
+    }
+}
+
+impl<T: SubAssign<T> + Copy> SubAssign for Complex<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.re -= other.re;
+        self.im -= other.im;
+    }
+}
+
+impl<T: Num> MulAssign for Complex<T> {
+    fn mul_assign(&mut self, other: Self) {
+        let (re, im) = (self.re, self.im);
+        self.re = re * other.re - im * other.im;
+        self.im = re * other.im + im * other.re;
+    }
+}
+
+impl<T: ComplexDiv> DivAssign for Complex<T> {
+    fn div_assign(&mut self, other: Self) {
+        *self = divide(self, &other);
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Self::Output {
+        return Complex {
+            re: -self.re,
+            im: -self.im
+        };
+    }
+}
+
+// ----------- REFERENCE OPERATORS ------------
+
+// Work on borrowed components, so types without Copy need no clones
+
+impl<'a, 'b, T> Add<&'b Complex<T>> for &'a Complex<T> where &'a T: Add<&'b T, Output = T> {
+    type Output = Complex<T>;
+
+    fn add(self, other: &'b Complex<T>) -> Complex<T> {
+        return Complex {
+            re: &self.re + &other.re,
+            im: &self.im + &other.im
+        };
+    }
+}
+
+impl<'a, 'b, T> Sub<&'b Complex<T>> for &'a Complex<T> where &'a T: Sub<&'b T, Output = T> {
+    type Output = Complex<T>;
+
+    fn sub(self, other: &'b Complex<T>) -> Complex<T> {
+        return Complex {
+            re: &self.re - &other.re,
+            im: &self.im - &other.im
+        };
+    }
+}
+
+impl<'a, 'b, T> Mul<&'b Complex<T>> for &'a Complex<T>
+    where T: Add<Output = T> + Sub<Output = T>, &'a T: Mul<&'b T, Output = T>
+{
+    type Output = Complex<T>;
+
+    fn mul(self, other: &'b Complex<T>) -> Complex<T> {
+        return Complex {
+            re: &self.re * &other.re - &self.im * &other.im,
+            im: &self.re * &other.im + &self.im * &other.re
+        };
+    }
+}
+
+impl<'a, 'b, T: ComplexDiv> Div<&'b Complex<T>> for &'a Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, other: &'b Complex<T>) -> Complex<T> {
+        return divide(self, other);
+    }
+}
+
+impl<'a, T> Neg for &'a Complex<T> where &'a T: Neg<Output = T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Complex<T> {
+        return Complex {
+            re: -&self.re,
+            im: -&self.im
+        };
+    }
+}
+
+// ----------- SCALAR OPERATORS ------------
+
+impl<T: Add<Output = T>> Add<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, other: T) -> Self::Output {
+        return Complex {
+            re: self.re + other,
+            im: self.im
+        };
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, other: T) -> Self::Output {
+        return Complex {
+            re: self.re - other,
+            im: self.im
+        };
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: T) -> Self::Output {
+        return Complex {
+            re: self.re * other,
+            im: self.im * other
+        };
+    }
+}
+
+// divides both parts, a zero scalar behaves like it does for T
+impl<T: Div<Output = T> + Copy> Div<T> for Complex<T> {
+    type Output = Complex<T>;
+
+    fn div(self, other: T) -> Self::Output {
+        return Complex {
+            re: self.re / other,
+            im: self.im / other
+        };
+    }
+}
+
+impl<T: AddAssign<T>> AddAssign<T> for Complex<T> {
+    fn add_assign(&mut self, other: T) {
+        self.re += other;
+    }
+}
+
+impl<T: SubAssign<T>> SubAssign<T> for Complex<T> {
+    fn sub_assign(&mut self, other: T) {
+        self.re -= other;
+    }
+}
+
+impl<T: MulAssign<T> + Copy> MulAssign<T> for Complex<T> {
+    fn mul_assign(&mut self, other: T) {
+        self.re *= other;
+        self.im *= other;
+    }
+}
+
+impl<T: DivAssign<T> + Copy> DivAssign<T> for Complex<T> {
+    fn div_assign(&mut self, other: T) {
+        self.re /= other;
+        self.im /= other;
+    }
+}
+
+impl<'a, 'b, T> Mul<&'b T> for &'a Complex<T> where &'a T: Mul<&'b T, Output = T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: &'b T) -> Complex<T> {
+        return Complex {
+            re: &self.re * other,
+            im: &self.im * other
+        };
+    }
+}
+
+// The scalar on the left can not be generic, every primitive gets its own impls
+macro_rules! impl_scalar_lhs {
+    ($($t:ty),*) => {$(
+        impl Add<Complex<$t>> for $t {
+            type Output = Complex<$t>;
+
+            fn add(self, other: Complex<$t>) -> Complex<$t> {
+                return other + self;
+            }
+        }
+
+        impl Sub<Complex<$t>> for $t {
+            type Output = Complex<$t>;
+
+            fn sub(self, other: Complex<$t>) -> Complex<$t> {
+                return Complex {
+                    re: self - other.re,
+                    im: (0 as $t) - other.im
+                };
+            }
+        }
+
+        impl Mul<Complex<$t>> for $t {
+            type Output = Complex<$t>;
+
+            fn mul(self, other: Complex<$t>) -> Complex<$t> {
+                return other * self;
+            }
+        }
+
+        impl Div<Complex<$t>> for $t {
+            type Output = Complex<$t>;
+
+            fn div(self, other: Complex<$t>) -> Complex<$t> {
+                return divide(&Complex { re: self, im: 0 as $t }, &other);
+            }
+        }
+    )*};
+}
+
+impl_scalar_lhs!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+// ----------- SUM AND PRODUCT ------------
+
+impl<T: Zero> Sum for Complex<T> {
+    fn sum<I: Iterator<Item = Complex<T>>>(iter: I) -> Self {
+        return iter.fold(Complex { re: T::zero(), im: T::zero() }, |sum, value| sum + value);
+    }
+}
+
+impl<'a, T: 'a + Zero + Copy> Sum<&'a Complex<T>> for Complex<T> {
+    fn sum<I: Iterator<Item = &'a Complex<T>>>(iter: I) -> Self {
+        return iter.copied().sum();
+    }
+}
+
+impl<T: Num> Product for Complex<T> {
+    fn product<I: Iterator<Item = Complex<T>>>(iter: I) -> Self {
+        return iter.fold(Complex::one(), |product, value| product * value);
+    }
+}
+
+impl<'a, T: 'a + Num> Product<&'a Complex<T>> for Complex<T> {
+    fn product<I: Iterator<Item = &'a Complex<T>>>(iter: I) -> Self {
+        return iter.copied().product();
+    }
+}
+
+// ----------- PARSING AND FORMATTING ------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParseComplexErrorKind {
+    Empty,
+    InvalidNumber,
+    MissingUnit,
+    MissingComma,
+    UnclosedParenthesis,
+    UnexpectedCharacter
+}
+
+// position is the byte offset in the parsed string where the problem starts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParseComplexError {
+    pub kind: ParseComplexErrorKind,
+    pub position: usize
+}
+
+impl Display for ParseComplexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self.kind {
+            ParseComplexErrorKind::Empty => "empty string",
+            ParseComplexErrorKind::InvalidNumber => "invalid number",
+            ParseComplexErrorKind::MissingUnit => "missing imaginary unit",
+            ParseComplexErrorKind::MissingComma => "missing comma",
+            ParseComplexErrorKind::UnclosedParenthesis => "unclosed parenthesis",
+            ParseComplexErrorKind::UnexpectedCharacter => "unexpected character"
+        };
+        return write!(f, "{} at {}", description, self.position);
+    }
+}
+
+impl std::error::Error for ParseComplexError {}
+
+fn parse_error<T>(kind: ParseComplexErrorKind, position: usize) -> Result<T, ParseComplexError> {
+    return Err(ParseComplexError { kind, position });
+}
+
+// A sign may be separated from its digits by spaces, a missing number means one
+fn parse_part<T: FromStr>(text: &str, offset: usize, imaginary: bool) -> Result<T, ParseComplexError> {
+    let start = offset + text.len() - text.trim_start().len();
+    let text = text.trim();
+    let (sign, digits) = match text.strip_prefix(|c| c == '+' || c == '-') {
+        Some(rest) => (&text[..1], rest.trim_start()),
+        None => ("", text)
+    };
+    let digits = if imaginary && digits.is_empty() { "1" } else { digits };
+    return match T::from_str(&format!("{}{}", sign, digits)) {
+        Ok(value) => Ok(value),
+        Err(_) => parse_error(ParseComplexErrorKind::InvalidNumber, start)
+    };
+}
+
+// Accepts "3+4i", "-2.5-1e3j", "5i", "-i", "7" and "(1,2)", with spaces
+// around the parts. Both i and j are taken as the imaginary unit.
+impl<T: FromStr + Default> FromStr for Complex<T> {
+    type Err = ParseComplexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let start = s.len() - s.trim_start().len();
+        let text = s.trim();
+        if text.is_empty() {
+            return parse_error(ParseComplexErrorKind::Empty, s.len());
+        }
+
+        if let Some(inner) = text.strip_prefix('(') {
+            let inner = match inner.strip_suffix(')') {
+                Some(inner) => inner,
+                None => return match inner.find(')') {
+                    Some(pos) => parse_error(ParseComplexErrorKind::UnexpectedCharacter, start + pos + 2),
+                    None => parse_error(ParseComplexErrorKind::UnclosedParenthesis, start + text.len())
+                }
+            };
+            let comma = match inner.find(',') {
+                Some(comma) => comma,
+                None => return parse_error(ParseComplexErrorKind::MissingComma, start + text.len() - 1)
+            };
+            return Ok(Complex {
+                re: parse_part(&inner[..comma], start + 1, false)?,
+                im: parse_part(&inner[comma + 1..], start + comma + 2, false)?
+            });
+        }
+
+        // the sign between the parts is the last one that is not in an exponent
+        let bytes = text.as_bytes();
+        let split = (1..bytes.len()).rev().find(|i| {
+            return (bytes[*i] == b'+' || bytes[*i] == b'-') && bytes[*i - 1] != b'e' && bytes[*i - 1] != b'E';
+        });
+        let unit = text.ends_with(|c| c == 'i' || c == 'j');
+        let body = if unit { &text[..text.len() - 1] } else { text };
+
+        return match split {
+            Some(_) if !unit => parse_error(ParseComplexErrorKind::MissingUnit, start + text.len()),
+            Some(split) => Ok(Complex {
+                re: parse_part(&body[..split], start, false)?,
+                im: parse_part(&body[split..], start + split, true)?
+            }),
+            None if unit => Ok(Complex { re: T::default(), im: parse_part(body, start, true)? }),
+            None => Ok(Complex { re: parse_part(body, start, false)?, im: T::default() })
+        };
+    }
+}
+
+// Formats with a chosen imaginary unit, the format flags apply to both parts:
+// format!("{:.2}", z.with_unit('j')) gives "1.00-2.50j"
+pub struct ComplexDisplay<'a, T> {
+    value: &'a Complex<T>,
+    unit: char
+}
+
+impl<T> Complex<T> {
+    pub fn with_unit(&self, unit: char) -> ComplexDisplay<'_, T> {
+        return ComplexDisplay { value: self, unit };
+    }
+}
+
+// {:+} only adds the sign of the real part, the imaginary one is always there.
+// Width, fill and alignment apply to the whole number.
+pub fn write_parts(f: &mut Formatter<'_>, re: String, imaginary: &[(String, char)]) -> std::fmt::Result {
+    let mut text = String::new();
+    if f.sign_plus() && !re.starts_with('-') {
+        text.push('+');
+    }
+    text.push_str(&re);
+    for (im, unit) in imaginary {
+        if !im.starts_with('-') {
+            text.push('+');
+        }
+        text.push_str(im);
+        text.push(*unit);
+    }
+
+    let padding = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+    let (before, after) = match f.align() {
+        Some(std::fmt::Alignment::Left) => (0, padding),
+        Some(std::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0)
+    };
+    let fill = f.fill().to_string();
+    return write!(f, "{}{}{}", fill.repeat(before), text, fill.repeat(after));
+}
+
+macro_rules! impl_complex_format {
+    ($($format:ident => $plain:literal, $precise:literal);*) => {$(
+        impl<'a, T: $format> $format for ComplexDisplay<'a, T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                let (re, im) = match f.precision() {
+                    Some(precision) => (format!($precise, self.value.re, precision = precision),
+                                        format!($precise, self.value.im, precision = precision)),
+                    None => (format!($plain, self.value.re), format!($plain, self.value.im))
+                };
+                return write_parts(f, re, &[(im, self.unit)]);
+            }
+        }
+
+        impl<T: $format> $format for Complex<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                return $format::fmt(&self.with_unit('i'), f);
+            }
+        }
+    )*};
+}
+
+impl_complex_format!(
+    Display => "{}", "{:.precision$}";
+    LowerExp => "{:e}", "{:.precision$e}";
+    UpperExp => "{:E}", "{:.precision$E}"
+);
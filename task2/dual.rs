@@ -0,0 +1,531 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign, Neg};
+use crate::complex::write_parts;
+use crate::num::{Zero, One, Num, Signed, Float};
+
+// a + bε with ε² = 0, so f(a + ε) = f(a) + f'(a)ε: evaluating a function on
+// a dual number gives its derivative next to the value, exact up to rounding.
+// The order compares the values first, like the derived PartialEq it also
+// looks at the derivatives.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Dual<T> {
+    pub value: T,
+    pub deriv: T
+}
+
+// One derivative per input, for gradients of functions of N variables
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct DualN<T, const N: usize> {
+    pub value: T,
+    pub grad: [T; N]
+}
+
+impl<T: Num> Dual<T> {
+    pub fn constant(value: T) -> Dual<T> {
+        return Dual { value, deriv: T::zero() };
+    }
+
+    pub fn variable(value: T) -> Dual<T> {
+        return Dual { value, deriv: T::one() };
+    }
+
+    // chain rule for g(self) with g' = slope at self.value
+    fn chain(&self, value: T, slope: T) -> Dual<T> {
+        return Dual { value, deriv: self.deriv * slope };
+    }
+
+    // chain rule for g(self, other) with the partial derivatives of g
+    fn chain2(&self, other: &Dual<T>, value: T, slope: T, other_slope: T) -> Dual<T> {
+        return Dual { value, deriv: self.deriv * slope + other.deriv * other_slope };
+    }
+}
+
+impl<T: Num, const N: usize> DualN<T, N> {
+    pub fn constant(value: T) -> DualN<T, N> {
+        return DualN { value, grad: [T::zero(); N] };
+    }
+
+    // the index-th input of the function
+    pub fn variable(value: T, index: usize) -> DualN<T, N> {
+        let mut grad = [T::zero(); N];
+        grad[index] = T::one();
+        return DualN { value, grad };
+    }
+
+    fn chain(&self, value: T, slope: T) -> DualN<T, N> {
+        return DualN { value, grad: self.grad.map(|d| d * slope) };
+    }
+
+    fn chain2(&self, other: &DualN<T, N>, value: T, slope: T, other_slope: T) -> DualN<T, N> {
+        return DualN {
+            value,
+            grad: std::array::from_fn(|i| self.grad[i] * slope + other.grad[i] * other_slope)
+        };
+    }
+}
+
+// d/dx f(x), f is usually generic so the same code runs on plain numbers
+pub fn derivative<T: Num, F: Fn(Dual<T>) -> Dual<T>>(f: F, x: T) -> T {
+    return f(Dual::variable(x)).deriv;
+}
+
+// All partial derivatives of f at xs in a single evaluation
+pub fn gradient<T: Num, const N: usize, F: Fn([DualN<T, N>; N]) -> DualN<T, N>>(f: F, xs: [T; N]) -> [T; N] {
+    let mut index = 0;
+    let inputs = xs.map(|x| {
+        index += 1;
+        return DualN::variable(x, index - 1);
+    });
+    return f(inputs).grad;
+}
+
+// ----------- OPERATORS ------------
+
+// Everything below only goes through the value and the two chain rules, so
+// it is written once for Dual and DualN: impl_dual!([generics] type)
+macro_rules! impl_dual {
+    ($([$($params:tt)*] $dual:ty),*) => {$(
+        impl<$($params)*> Add for $dual where T: Num {
+            type Output = $dual;
+
+            fn add(self, other: $dual) -> $dual {
+                return self.chain2(&other, self.value + other.value, T::one(), T::one());
+            }
+        }
+
+        impl<$($params)*> Sub for $dual where T: Num {
+            type Output = $dual;
+
+            fn sub(self, other: $dual) -> $dual {
+                return self.chain2(&other, self.value - other.value, T::one(), T::zero() - T::one());
+            }
+        }
+
+        impl<$($params)*> Mul for $dual where T: Num {
+            type Output = $dual;
+
+            fn mul(self, other: $dual) -> $dual {
+                return self.chain2(&other, self.value * other.value, other.value, self.value);
+            }
+        }
+
+        // (a / b)' = a' / b - a b' / b²
+        impl<$($params)*> Div for $dual where T: Num {
+            type Output = $dual;
+
+            fn div(self, other: $dual) -> $dual {
+                let quotient = self.value / other.value;
+                return self.chain2(&other, quotient, T::one() / other.value,
+                                   (T::zero() - quotient) / other.value);
+            }
+        }
+
+        impl<$($params)*> Neg for $dual where T: Num {
+            type Output = $dual;
+
+            fn neg(self) -> $dual {
+                return self.chain(T::zero() - self.value, T::zero() - T::one());
+            }
+        }
+
+        impl<$($params)*> AddAssign for $dual where T: Num {
+            fn add_assign(&mut self, other: $dual) {
+                *self = *self + other;
+            }
+        }
+
+        impl<$($params)*> SubAssign for $dual where T: Num {
+            fn sub_assign(&mut self, other: $dual) {
+                *self = *self - other;
+            }
+        }
+
+        impl<$($params)*> MulAssign for $dual where T: Num {
+            fn mul_assign(&mut self, other: $dual) {
+                *self = *self * other;
+            }
+        }
+
+        impl<$($params)*> DivAssign for $dual where T: Num {
+            fn div_assign(&mut self, other: $dual) {
+                *self = *self / other;
+            }
+        }
+
+        // scalars are constants
+        impl<$($params)*> Add<T> for $dual where T: Num {
+            type Output = $dual;
+
+            fn add(self, other: T) -> $dual {
+                return self.chain(self.value + other, T::one());
+            }
+        }
+
+        impl<$($params)*> Sub<T> for $dual where T: Num {
+            type Output = $dual;
+
+            fn sub(self, other: T) -> $dual {
+                return self.chain(self.value - other, T::one());
+            }
+        }
+
+        impl<$($params)*> Mul<T> for $dual where T: Num {
+            type Output = $dual;
+
+            fn mul(self, other: T) -> $dual {
+                return self.chain(self.value * other, other);
+            }
+        }
+
+        impl<$($params)*> Div<T> for $dual where T: Num {
+            type Output = $dual;
+
+            fn div(self, other: T) -> $dual {
+                return self.chain(self.value / other, T::one() / other);
+            }
+        }
+
+        impl<$($params)*> Zero for $dual where T: Num {
+            fn zero() -> $dual {
+                return <$dual>::constant(T::zero());
+            }
+
+            fn is_zero(&self) -> bool {
+                return *self == <$dual>::constant(T::zero());
+            }
+        }
+
+        impl<$($params)*> One for $dual where T: Num {
+            fn one() -> $dual {
+                return <$dual>::constant(T::one());
+            }
+        }
+
+        impl<$($params)*> Signed for $dual where T: Signed {
+            // abs flips the derivative with the value, at 0 it keeps it
+            fn is_negative(self) -> bool {
+                return self.value.is_negative();
+            }
+
+            fn signum(self) -> $dual {
+                return <$dual>::constant(self.value.signum());
+            }
+        }
+
+        // Generic code over T: Float runs unchanged on dual numbers. Constants
+        // have no derivative, to_f64 drops it.
+        impl<$($params)*> Float for $dual where T: Float {
+            fn from_f64(value: f64) -> $dual {
+                return <$dual>::constant(T::from_f64(value));
+            }
+
+            fn to_f64(self) -> f64 {
+                return self.value.to_f64();
+            }
+
+            fn nan() -> $dual {
+                return <$dual>::constant(T::nan());
+            }
+
+            fn epsilon() -> $dual {
+                return <$dual>::constant(T::epsilon());
+            }
+
+            fn pi() -> $dual {
+                return <$dual>::constant(T::pi());
+            }
+
+            fn is_nan(self) -> bool {
+                return self.value.is_nan();
+            }
+
+            fn sqrt(self) -> $dual {
+                let root = self.value.sqrt();
+                return self.chain(root, T::one() / (T::two() * root));
+            }
+
+            fn hypot(self, other: $dual) -> $dual {
+                let length = self.value.hypot(other.value);
+                return self.chain2(&other, length, self.value / length, other.value / length);
+            }
+
+            fn copysign(self, sign: $dual) -> $dual {
+                let value = self.value.copysign(sign.value);
+                let slope = if value.is_negative() == self.value.is_negative() { T::one() } else { -T::one() };
+                return self.chain(value, slope);
+            }
+
+            fn exp(self) -> $dual {
+                let exp = self.value.exp();
+                return self.chain(exp, exp);
+            }
+
+            fn ln(self) -> $dual {
+                return self.chain(self.value.ln(), T::one() / self.value);
+            }
+
+            // x^y has the partial ln(x) x^y in y, which only exists for x > 0,
+            // elsewhere the exponent is taken as a constant
+            fn powf(self, exponent: $dual) -> $dual {
+                let power = self.value.powf(exponent.value);
+                let slope = if exponent.value.is_zero() {
+                    T::zero()
+                } else {
+                    exponent.value * self.value.powf(exponent.value - T::one())
+                };
+                let exponent_slope = if self.value > T::zero() { power * self.value.ln() } else { T::zero() };
+                return self.chain2(&exponent, power, slope, exponent_slope);
+            }
+
+            fn sin(self) -> $dual {
+                let (sin, cos) = self.value.sin_cos();
+                return self.chain(sin, cos);
+            }
+
+            fn cos(self) -> $dual {
+                let (sin, cos) = self.value.sin_cos();
+                return self.chain(cos, -sin);
+            }
+
+            fn sin_cos(self) -> ($dual, $dual) {
+                let (sin, cos) = self.value.sin_cos();
+                return (self.chain(sin, cos), self.chain(cos, -sin));
+            }
+
+            fn asin(self) -> $dual {
+                let slope = T::one() / (T::one() - self.value * self.value).sqrt();
+                return self.chain(self.value.asin(), slope);
+            }
+
+            fn acos(self) -> $dual {
+                let slope = -T::one() / (T::one() - self.value * self.value).sqrt();
+                return self.chain(self.value.acos(), slope);
+            }
+
+            // self is y, other is x
+            fn atan2(self, other: $dual) -> $dual {
+                let square = self.value * self.value + other.value * other.value;
+                return self.chain2(&other, self.value.atan2(other.value), other.value / square,
+                                   -self.value / square);
+            }
+
+            fn sinh(self) -> $dual {
+                return self.chain(self.value.sinh(), self.value.cosh());
+            }
+
+            fn cosh(self) -> $dual {
+                return self.chain(self.value.cosh(), self.value.sinh());
+            }
+        }
+    )*};
+}
+
+impl_dual!([T] Dual<T>, [T, const N: usize] DualN<T, N>);
+
+macro_rules! impl_scalar_lhs {
+    ($($t:ty),*) => {$(
+        impl Add<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn add(self, other: Dual<$t>) -> Dual<$t> {
+                return other + self;
+            }
+        }
+
+        impl Sub<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn sub(self, other: Dual<$t>) -> Dual<$t> {
+                return -other + self;
+            }
+        }
+
+        impl Mul<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn mul(self, other: Dual<$t>) -> Dual<$t> {
+                return other * self;
+            }
+        }
+
+        impl Div<Dual<$t>> for $t {
+            type Output = Dual<$t>;
+
+            fn div(self, other: Dual<$t>) -> Dual<$t> {
+                return Dual::constant(self) / other;
+            }
+        }
+
+        impl<const N: usize> Add<DualN<$t, N>> for $t {
+            type Output = DualN<$t, N>;
+
+            fn add(self, other: DualN<$t, N>) -> DualN<$t, N> {
+                return other + self;
+            }
+        }
+
+        impl<const N: usize> Sub<DualN<$t, N>> for $t {
+            type Output = DualN<$t, N>;
+
+            fn sub(self, other: DualN<$t, N>) -> DualN<$t, N> {
+                return -other + self;
+            }
+        }
+
+        impl<const N: usize> Mul<DualN<$t, N>> for $t {
+            type Output = DualN<$t, N>;
+
+            fn mul(self, other: DualN<$t, N>) -> DualN<$t, N> {
+                return other * self;
+            }
+        }
+
+        impl<const N: usize> Div<DualN<$t, N>> for $t {
+            type Output = DualN<$t, N>;
+
+            fn div(self, other: DualN<$t, N>) -> DualN<$t, N> {
+                return DualN::constant(self) / other;
+            }
+        }
+    )*};
+}
+
+impl_scalar_lhs!(f32, f64);
+
+// ----------- FUNCTIONS ------------
+
+// The Float functions with no counterpart in the trait
+impl<T: Float> Dual<T> {
+    pub fn powi(&self, exponent: i32) -> Dual<T> {
+        return self.powf(Dual::constant(T::from_f64(exponent as f64)));
+    }
+
+    pub fn tan(&self) -> Dual<T> {
+        let tan = self.value.sin() / self.value.cos();
+        return self.chain(tan, T::one() + tan * tan);
+    }
+
+    pub fn tanh(&self) -> Dual<T> {
+        let tanh = self.value.sinh() / self.value.cosh();
+        return self.chain(tanh, T::one() - tanh * tanh);
+    }
+}
+
+// ----------- FORMATTING ------------
+
+// "3+2ε", the flags apply to both parts like for Complex
+impl<T: Display> Display for Dual<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (value, deriv) = match f.precision() {
+            Some(precision) => (format!("{:.*}", precision, self.value), format!("{:.*}", precision, self.deriv)),
+            None => (self.value.to_string(), self.deriv.to_string())
+        };
+        return write_parts(f, value, &[(deriv, 'ε')]);
+    }
+}
+
+#[test]
+fn test_dual_operations() {
+    let x = Dual::variable(3.0);
+    let c = Dual::constant(2.0);
+    assert_eq!(x + c, Dual { value: 5.0, deriv: 1.0 });
+    assert_eq!(x - c, Dual { value: 1.0, deriv: 1.0 });
+    assert_eq!(c - x, Dual { value: -1.0, deriv: -1.0 });
+    assert_eq!(x * x, Dual { value: 9.0, deriv: 6.0 });
+    assert_eq!(c / x, Dual { value: 2.0 / 3.0, deriv: -2.0 / 9.0 });
+    assert_eq!(-x, Dual { value: -3.0, deriv: -1.0 });
+    assert_eq!(2.0 * x + 1.0, Dual { value: 7.0, deriv: 2.0 });
+    assert_eq!(1.0 - x / 2.0, Dual { value: -0.5, deriv: -0.5 });
+    assert_eq!(6.0 / x, Dual { value: 2.0, deriv: -2.0 / 3.0 });
+
+    let mut y = x;
+    y *= x;
+    y += c;
+    y -= x;
+    y /= c;
+    assert_eq!(y, Dual { value: 4.0, deriv: 2.5 });
+
+    assert!(Dual::variable(1.0) > Dual::constant(1.0));
+    assert!(Dual::constant(-2.0) < Dual::variable(1.0));
+    assert!(Dual::<f64>::zero().is_zero() && !Dual::variable(0.0).is_zero());
+    assert_eq!(Signed::abs(Dual::variable(-2.0)), Dual { value: 2.0, deriv: -1.0 });
+
+    assert_eq!(format!("{}", Dual { value: 3.0, deriv: -2.0 }), "3-2ε");
+    assert_eq!(format!("{:.1}", Dual { value: 0.25, deriv: 1.0 }), "0.2+1.0ε");
+}
+
+#[test]
+fn test_derivative() {
+    use crate::ratio::Ratio;
+
+    // (x³ - 2x) / (x + 1) at x = 2 is (10 * 3 - 4) / 9 = 26 / 9, exactly
+    let f = |x: Dual<Ratio<i64>>| (x * x * x - x * Ratio::from_integer(2)) / (x + Ratio::from_integer(1));
+    assert_eq!(derivative(f, Ratio::from_integer(2)), Ratio::new(26, 9));
+    assert_eq!(derivative(|x| x * x, 7i64), 14);
+
+    fn generic<T: Float>(x: T) -> T {
+        return (x * x + T::one()).sqrt() * x.sin() + x.exp().ln() - T::pi().cos() * x;
+    }
+    let x = 0.7;
+    let expected = x / (x * x + 1.0f64).sqrt() * x.sin() + (x * x + 1.0f64).sqrt() * x.cos() + 1.0 + 1.0;
+    assert!((derivative(generic, x) - expected).abs() < 1e-14);
+    assert_eq!(generic(Dual::variable(x)).value, generic(x));
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-12 * (1.0 + b.abs());
+    assert!(close(derivative(|x| x.tan(), 0.5), 1.0 / (0.5f64.cos() * 0.5f64.cos())));
+    assert!(close(derivative(|x| x.tanh(), 0.5), 1.0 - 0.5f64.tanh() * 0.5f64.tanh()));
+    assert!(close(derivative(|x| x.asin() + x.acos(), 0.3), 0.0));
+    assert!(close(derivative(|x| x.sinh() - x.cosh(), 0.3), (-0.3f64).exp()));
+    assert!(close(derivative(|x| x.powi(3), -2.0), 12.0));
+    assert!(close(derivative(|x| x.powf(x), 2.0), 4.0 * (2.0f64.ln() + 1.0)));
+    assert!(close(derivative(|x| Signed::abs(x), -2.0), -1.0));
+    assert!(close(derivative(|x| x.copysign(Dual::constant(-1.0)), 2.0), -1.0));
+    assert_eq!(derivative(|x: Dual<f64>| x.powf(Dual::constant(0.0)), 0.0), 0.0);
+
+    // Newton's method with an exact derivative finds the cube root of 2
+    let f = |x: Dual<f64>| x * x * x - 2.0;
+    let mut x = 1.0;
+    for _ in 0..8 {
+        x -= f(Dual::variable(x)).value / derivative(f, x);
+    }
+    assert!((x - 2.0f64.powf(1.0 / 3.0)).abs() < 1e-15);
+}
+
+#[test]
+fn test_gradient() {
+    // f(x, y) = x y + sin(x) / y
+    let f = |[x, y]: [DualN<f64, 2>; 2]| x * y + x.sin() / y;
+    let [dx, dy] = gradient(f, [1.0, 2.0]);
+    assert!((dx - (2.0 + 1.0f64.cos() / 2.0)).abs() < 1e-15);
+    assert!((dy - (1.0 - 1.0f64.sin() / 4.0)).abs() < 1e-15);
+
+    // the hypot and atan2 partials are the polar coordinates' derivatives
+    let [dx, dy] = gradient(|[x, y]| x.hypot(y), [3.0, 4.0]);
+    assert_eq!([dx, dy], [0.6, 0.8]);
+    let [dx, dy] = gradient(|[x, y]| y.atan2(x), [3.0, 4.0]);
+    assert!((dx + 0.16).abs() < 1e-15 && (dy - 0.12).abs() < 1e-15);
+
+    let [dx, dy, dz] = gradient(|[x, y, z]| x * y * z + 2.0 * x - 1.0 / z, [2.0, 3.0, 4.0]);
+    assert_eq!([dx, dy, dz], [14.0, 8.0, 6.0 + 1.0 / 16.0]);
+
+    // a single input gives the plain derivative
+    assert_eq!(gradient(|[x]| x.exp() * x.cos(), [0.4])[0], derivative(|x| x.exp() * x.cos(), 0.4));
+}
+
+#[test]
+fn test_against_finite_differences() {
+    use crate::prng::{Rng, Xoshiro256};
+
+    fn f<T: Float>(x: T) -> T {
+        return (x.sin() * x.exp()).hypot(x) + (x * x + T::one()).ln().powf(x.cos());
+    }
+
+    let mut rng = Xoshiro256::new(49);
+    for _ in 0..1000 {
+        let x: f64 = rng.range(-3.0..3.0);
+        let h = 1e-6;
+        let estimate = (f(x + h) - f(x - h)) / (2.0 * h);
+        let exact = derivative(f, x);
+        assert!((exact - estimate).abs() < 1e-6 * (1.0 + exact.abs()), "{} {} {}", x, exact, estimate);
+    }
+}
@@ -0,0 +1,302 @@
+use std::f64::consts::PI;
+use crate::Complex;
+
+const ZERO: Complex<f64> = Complex { re: 0.0, im: 0.0 };
+
+enum Algorithm {
+    // lengths 0 and 1 are their own transform
+    Identity,
+    Radix2 {
+        // e^(-2πik/n) for k < n/2
+        twiddles: Vec<Complex<f64>>
+    },
+    // Any length as a convolution of power of two length m >= 2n - 1
+    Bluestein {
+        // e^(-πik²/n) for k < n
+        chirp: Vec<Complex<f64>>,
+        // transform of the conjugated chirp, wrapped around to length m
+        filter: Vec<Complex<f64>>,
+        inner: Box<FftPlan>
+    }
+}
+
+// Transforms of one length, the twiddle factors are computed once and reused.
+// The forward transform is X_k = Σ x_j e^(-2πijk/n), the inverse one is scaled by 1/n.
+pub struct FftPlan {
+    len: usize,
+    algorithm: Algorithm
+}
+
+impl FftPlan {
+    pub fn new(len: usize) -> FftPlan {
+        let algorithm = if len <= 1 {
+            Algorithm::Identity
+        } else if len.is_power_of_two() {
+            let twiddles = (0..len / 2).map(|k| unit_root(k as f64, len as f64)).collect();
+            Algorithm::Radix2 { twiddles }
+        } else {
+            // k² is reduced mod 2n first, the angle stays accurate for large k
+            let chirp: Vec<Complex<f64>> = (0..len)
+                .map(|k| unit_root(((k * k) % (2 * len)) as f64, 2.0 * len as f64))
+                .collect();
+            let inner = FftPlan::new((2 * len - 1).next_power_of_two());
+            let mut filter = vec![ZERO; inner.len];
+            filter[0] = chirp[0].conj();
+            for k in 1..len {
+                filter[k] = chirp[k].conj();
+                filter[inner.len - k] = chirp[k].conj();
+            }
+            inner.forward(&mut filter);
+            Algorithm::Bluestein { chirp, filter, inner: Box::new(inner) }
+        };
+        return FftPlan { len, algorithm };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.len;
+    }
+
+    pub fn forward(&self, data: &mut [Complex<f64>]) {
+        assert_eq!(data.len(), self.len, "data length does not match the plan");
+        match &self.algorithm {
+            Algorithm::Identity => {}
+            Algorithm::Radix2 { twiddles } => radix2(data, twiddles),
+            Algorithm::Bluestein { chirp, filter, inner } => {
+                let mut scratch = vec![ZERO; inner.len];
+                for k in 0..self.len {
+                    scratch[k] = data[k] * chirp[k];
+                }
+                inner.forward(&mut scratch);
+                for (value, weight) in scratch.iter_mut().zip(filter) {
+                    *value = *value * *weight;
+                }
+                inner.inverse(&mut scratch);
+                for k in 0..self.len {
+                    data[k] = scratch[k] * chirp[k];
+                }
+            }
+        }
+    }
+
+    // the inverse transform is the forward one of the conjugate, conjugated again
+    pub fn inverse(&self, data: &mut [Complex<f64>]) {
+        data.iter_mut().for_each(|value| *value = value.conj());
+        self.forward(data);
+        let scale = 1.0 / self.len as f64;
+        data.iter_mut().for_each(|value| *value = value.conj() * scale);
+    }
+}
+
+fn unit_root(k: f64, n: f64) -> Complex<f64> {
+    return Complex::<f64>::from_polar(1.0, -2.0 * PI * k / n);
+}
+
+// In-place iterative Cooley–Tukey: bit reversed order first, then butterflies
+// of growing size
+fn radix2(data: &mut [Complex<f64>], twiddles: &[Complex<f64>]) {
+    let len = data.len();
+    let shift = usize::BITS - len.trailing_zeros();
+    for i in 0..len {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let half = size / 2;
+        let step = len / size;
+        for start in (0..len).step_by(size) {
+            for k in 0..half {
+                let even = data[start + k];
+                let odd = data[start + k + half] * twiddles[k * step];
+                data[start + k] = even + odd;
+                data[start + k + half] = even - odd;
+            }
+        }
+        size *= 2;
+    }
+}
+
+pub fn fft(data: &mut [Complex<f64>]) {
+    FftPlan::new(data.len()).forward(data);
+}
+
+pub fn ifft(data: &mut [Complex<f64>]) {
+    FftPlan::new(data.len()).inverse(data);
+}
+
+// Reference O(n²) transform
+pub fn dft(data: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let len = data.len();
+    return (0..len).map(|k| {
+        return data.iter().enumerate()
+            .map(|(j, value)| *value * unit_root(((j * k) % len) as f64, len as f64))
+            .fold(ZERO, |sum, value| sum + value);
+    }).collect();
+}
+
+// ----------- REAL INPUT ------------
+
+// The spectrum of real input is conjugate symmetric, so only the first
+// n/2 + 1 values are kept. Even lengths pack pairs of samples into one
+// complex value and use a transform of half the length.
+pub struct RealFftPlan {
+    len: usize,
+    half: FftPlan,
+    // e^(-2πik/n) for k < n/2, only used for even lengths
+    twiddles: Vec<Complex<f64>>
+}
+
+impl RealFftPlan {
+    pub fn new(len: usize) -> RealFftPlan {
+        if len % 2 == 1 {
+            return RealFftPlan { len, half: FftPlan::new(len), twiddles: vec![] };
+        }
+        let twiddles = (0..len / 2).map(|k| unit_root(k as f64, len as f64)).collect();
+        return RealFftPlan { len, half: FftPlan::new(len / 2), twiddles };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.len;
+    }
+
+    pub fn forward(&self, input: &[f64]) -> Vec<Complex<f64>> {
+        assert_eq!(input.len(), self.len, "input length does not match the plan");
+        if self.len % 2 == 1 {
+            let mut data: Vec<Complex<f64>> = input.iter().map(|x| Complex { re: *x, im: 0.0 }).collect();
+            self.half.forward(&mut data);
+            data.truncate(self.len / 2 + 1);
+            return data;
+        }
+        if self.len == 0 {
+            return vec![ZERO];
+        }
+
+        let half = self.len / 2;
+        let mut packed: Vec<Complex<f64>> = input.chunks_exact(2).map(|pair| Complex { re: pair[0], im: pair[1] }).collect();
+        self.half.forward(&mut packed);
+
+        let mut spectrum = Vec::with_capacity(half + 1);
+        for k in 0..=half {
+            let z = packed[k % half];
+            let mirror = packed[(half - k) % half].conj();
+            let even = (z + mirror) * 0.5;
+            let odd = (z - mirror) * 0.5;
+            // odd / i is the transform of the odd samples
+            let odd = Complex { re: odd.im, im: -odd.re };
+            let twiddle = if k < half { self.twiddles[k] } else { Complex { re: -1.0, im: 0.0 } };
+            spectrum.push(even + twiddle * odd);
+        }
+        return spectrum;
+    }
+
+    pub fn inverse(&self, spectrum: &[Complex<f64>]) -> Vec<f64> {
+        assert_eq!(spectrum.len(), self.len / 2 + 1, "spectrum length does not match the plan");
+        if self.len % 2 == 1 {
+            let mut data = spectrum.to_vec();
+            for k in spectrum.len()..self.len {
+                data.push(spectrum[self.len - k].conj());
+            }
+            self.half.inverse(&mut data);
+            return data.iter().map(|value| value.re).collect();
+        }
+        if self.len == 0 {
+            return vec![];
+        }
+
+        let half = self.len / 2;
+        let mut packed = Vec::with_capacity(half);
+        for k in 0..half {
+            let mirror = spectrum[half - k].conj();
+            let even = (spectrum[k] + mirror) * 0.5;
+            let odd = (spectrum[k] - mirror) * self.twiddles[k].conj() * 0.5;
+            packed.push(even + Complex { re: -odd.im, im: odd.re });
+        }
+        self.half.inverse(&mut packed);
+        return packed.iter().flat_map(|value| [value.re, value.im]).collect();
+    }
+}
+
+pub fn rfft(input: &[f64]) -> Vec<Complex<f64>> {
+    return RealFftPlan::new(input.len()).forward(input);
+}
+
+pub fn irfft(spectrum: &[Complex<f64>], len: usize) -> Vec<f64> {
+    return RealFftPlan::new(len).inverse(spectrum);
+}
+
+#[cfg(test)]
+fn assert_close(actual: &[Complex<f64>], expected: &[Complex<f64>]) {
+    let scale = 1.0 + expected.iter().map(|value| value.norm()).fold(0.0, f64::max);
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((*a - *e).norm() < 1e-10 * scale, "{} != {}", a, e);
+    }
+}
+
+#[test]
+fn test_known_transforms() {
+    let one = Complex { re: 1.0, im: 0.0 };
+
+    let mut impulse = vec![ZERO; 8];
+    impulse[0] = one;
+    fft(&mut impulse);
+    assert_close(&impulse, &vec![one; 8]);
+
+    let mut constant = vec![one; 6];
+    fft(&mut constant);
+    let mut expected = vec![ZERO; 6];
+    expected[0] = one * 6.0;
+    assert_close(&constant, &expected);
+
+    let mut data = vec![one, Complex { re: 2.0, im: 0.0 }, Complex { re: 3.0, im: 0.0 }, Complex { re: 4.0, im: 0.0 }];
+    fft(&mut data);
+    assert_close(&data, &[
+        Complex { re: 10.0, im: 0.0 }, Complex { re: -2.0, im: 2.0 },
+        Complex { re: -2.0, im: 0.0 }, Complex { re: -2.0, im: -2.0 }
+    ]);
+
+    let mut empty: Vec<Complex<f64>> = vec![];
+    fft(&mut empty);
+    ifft(&mut empty);
+    assert_eq!(rfft(&[]), vec![ZERO]);
+    assert_eq!(irfft(&[ZERO], 0), Vec::<f64>::new());
+}
+
+#[test]
+fn test_against_dft() {
+    use crate::prng::Xoshiro256;
+    let mut rng = Xoshiro256::new(40);
+    let lengths = (1..=40).chain([64, 100, 127, 128, 243, 256, 1000]);
+    for len in lengths {
+        let input: Vec<Complex<f64>> = (0..len).map(|_| Complex::random(&mut rng, -1.0..1.0)).collect();
+        let expected = dft(&input);
+        let plan = FftPlan::new(len);
+        assert_eq!(plan.len(), len);
+
+        let mut data = input.clone();
+        plan.forward(&mut data);
+        assert_close(&data, &expected);
+
+        // Parseval: the energy is kept up to the factor n
+        let energy: f64 = input.iter().map(|value| value.norm_sqr()).sum();
+        let spectrum_energy: f64 = data.iter().map(|value| value.norm_sqr()).sum();
+        assert!((spectrum_energy / len as f64 - energy).abs() < 1e-9 * energy);
+
+        plan.inverse(&mut data);
+        assert_close(&data, &input);
+
+        let real: Vec<f64> = input.iter().map(|value| value.re).collect();
+        let real_plan = RealFftPlan::new(len);
+        assert_eq!(real_plan.len(), len);
+        let spectrum = real_plan.forward(&real);
+        let expected = dft(&real.iter().map(|x| Complex { re: *x, im: 0.0 }).collect::<Vec<_>>());
+        assert_close(&spectrum, &expected[..len / 2 + 1]);
+
+        let restored = real_plan.inverse(&spectrum);
+        assert_eq!(restored.len(), len);
+        assert!(restored.iter().zip(&real).all(|(a, b)| (a - b).abs() < 1e-10));
+    }
+}
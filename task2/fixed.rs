@@ -0,0 +1,579 @@
+// Binary fixed-point numbers, shared by the task2 crates so they work as
+// Complex components and Rectangle coordinates: mod num; mod fixed; mod complex;
+#![allow(dead_code)]
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+use crate::num::{Zero, One, Signed};
+use crate::complex::{Complex, ComplexDiv, ComplexError, smith_div};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Floor,
+    Ceil,
+    TowardZero,
+    // halves away from zero
+    Nearest,
+    // halves to the even neighbour, the default everywhere
+    NearestEven
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParseFixedError {
+    Invalid,
+    OutOfRange
+}
+
+impl Display for ParseFixedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ParseFixedError::Invalid => write!(f, "invalid fixed-point number"),
+            ParseFixedError::OutOfRange => write!(f, "number does not fit the fixed-point type")
+        };
+    }
+}
+
+impl std::error::Error for ParseFixedError {}
+
+// The value is bits / 2^FRAC, stored in an i32 or i64. All results only depend
+// on the bits, never on the float unit of the machine. The operators round
+// products and quotients to nearest even and panic on overflow, the checked_
+// and saturating_ methods handle it instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed<T, const FRAC: u32> {
+    bits: T
+}
+
+// n / d with the given rounding, d is not zero. Every intermediate value of
+// both storage types fits an i128.
+fn divide(n: i128, d: i128, rounding: Rounding) -> i128 {
+    let (quotient, remainder) = (n / d, n % d);
+    if remainder == 0 {
+        return quotient;
+    }
+    // the exact quotient lies between quotient and its neighbour away from zero
+    let away = if (n < 0) != (d < 0) { quotient - 1 } else { quotient + 1 };
+    let (below, above) = (remainder.abs(), d.abs() - remainder.abs());
+    let up = match rounding {
+        Rounding::Floor => away < quotient,
+        Rounding::Ceil => away > quotient,
+        Rounding::TowardZero => false,
+        Rounding::Nearest => below >= above,
+        Rounding::NearestEven => below > above || (below == above && quotient % 2 != 0)
+    };
+    return if up { away } else { quotient };
+}
+
+macro_rules! impl_fixed {
+    ($($t:ident),*) => {$(
+        impl<const FRAC: u32> Fixed<$t, FRAC> {
+            // one has to be representable, which leaves FRAC < BITS - 1
+            const SCALE: $t = {
+                assert!(FRAC < <$t>::BITS - 1, "too many fraction bits for the storage type");
+                1 << FRAC
+            };
+
+            pub const ZERO: Fixed<$t, FRAC> = Fixed { bits: 0 };
+            pub const ONE: Fixed<$t, FRAC> = Fixed { bits: Self::SCALE };
+            pub const MIN: Fixed<$t, FRAC> = Fixed { bits: <$t>::MIN };
+            pub const MAX: Fixed<$t, FRAC> = Fixed { bits: <$t>::MAX };
+            // the smallest positive value, 2^-FRAC
+            pub const EPSILON: Fixed<$t, FRAC> = Fixed { bits: 1 };
+
+            pub const fn from_bits(bits: $t) -> Fixed<$t, FRAC> {
+                return Fixed { bits };
+            }
+
+            pub fn to_bits(self) -> $t {
+                return self.bits;
+            }
+
+            pub fn from_int(value: $t) -> Option<Fixed<$t, FRAC>> {
+                return value.checked_mul(Self::SCALE).map(Self::from_bits);
+            }
+
+            pub fn to_int(self, rounding: Rounding) -> $t {
+                return divide(self.bits as i128, Self::SCALE as i128, rounding) as $t;
+            }
+
+            fn narrow(wide: i128) -> Option<Fixed<$t, FRAC>> {
+                return <$t>::try_from(wide).ok().map(Self::from_bits);
+            }
+
+            fn saturate(wide: i128) -> Fixed<$t, FRAC> {
+                return Self::from_bits(wide.clamp(<$t>::MIN as i128, <$t>::MAX as i128) as $t);
+            }
+
+            // ----------- CONVERSIONS ------------
+
+            pub fn from_f64(value: f64) -> Option<Fixed<$t, FRAC>> {
+                return Self::from_f64_with(value, Rounding::NearestEven);
+            }
+
+            // Scaling by a power of two is exact, only the rounding to bits is not
+            pub fn from_f64_with(value: f64, rounding: Rounding) -> Option<Fixed<$t, FRAC>> {
+                let scaled = value * Self::SCALE as f64;
+                let rounded = match rounding {
+                    Rounding::Floor => scaled.floor(),
+                    Rounding::Ceil => scaled.ceil(),
+                    Rounding::TowardZero => scaled.trunc(),
+                    Rounding::Nearest => scaled.round(),
+                    Rounding::NearestEven => scaled.round_ties_even()
+                };
+                // MAX + 1 is a power of two and exact as a float, MAX itself may not be
+                if !rounded.is_finite() || rounded < <$t>::MIN as f64 || rounded >= -(<$t>::MIN as f64) {
+                    return None;
+                }
+                return Some(Self::from_bits(rounded as $t));
+            }
+
+            pub fn to_f64(self) -> f64 {
+                return self.bits as f64 / Self::SCALE as f64;
+            }
+
+            // The same value with TO fraction bits, rounded when bits are dropped
+            pub fn rescale<const TO: u32>(self, rounding: Rounding) -> Option<Fixed<$t, TO>> {
+                let bits = self.bits as i128;
+                if TO >= FRAC {
+                    return Fixed::<$t, TO>::narrow(bits << (TO - FRAC));
+                }
+                return Fixed::<$t, TO>::narrow(divide(bits, 1 << (FRAC - TO), rounding));
+            }
+
+            // ----------- CHECKED AND SATURATING ------------
+
+            pub fn checked_add(self, other: Fixed<$t, FRAC>) -> Option<Fixed<$t, FRAC>> {
+                return self.bits.checked_add(other.bits).map(Self::from_bits);
+            }
+
+            pub fn checked_sub(self, other: Fixed<$t, FRAC>) -> Option<Fixed<$t, FRAC>> {
+                return self.bits.checked_sub(other.bits).map(Self::from_bits);
+            }
+
+            pub fn checked_neg(self) -> Option<Fixed<$t, FRAC>> {
+                return self.bits.checked_neg().map(Self::from_bits);
+            }
+
+            pub fn checked_mul(self, other: Fixed<$t, FRAC>) -> Option<Fixed<$t, FRAC>> {
+                return self.checked_mul_with(other, Rounding::NearestEven);
+            }
+
+            pub fn checked_mul_with(self, other: Fixed<$t, FRAC>, rounding: Rounding) -> Option<Fixed<$t, FRAC>> {
+                let product = self.bits as i128 * other.bits as i128;
+                return Self::narrow(divide(product, Self::SCALE as i128, rounding));
+            }
+
+            pub fn checked_div(self, other: Fixed<$t, FRAC>) -> Option<Fixed<$t, FRAC>> {
+                return self.checked_div_with(other, Rounding::NearestEven);
+            }
+
+            pub fn checked_div_with(self, other: Fixed<$t, FRAC>, rounding: Rounding) -> Option<Fixed<$t, FRAC>> {
+                if other.bits == 0 {
+                    return None;
+                }
+                return Self::narrow(divide((self.bits as i128) << FRAC, other.bits as i128, rounding));
+            }
+
+            pub fn checked_rem(self, other: Fixed<$t, FRAC>) -> Option<Fixed<$t, FRAC>> {
+                return self.bits.checked_rem(other.bits).map(Self::from_bits);
+            }
+
+            pub fn saturating_add(self, other: Fixed<$t, FRAC>) -> Fixed<$t, FRAC> {
+                return Self::from_bits(self.bits.saturating_add(other.bits));
+            }
+
+            pub fn saturating_sub(self, other: Fixed<$t, FRAC>) -> Fixed<$t, FRAC> {
+                return Self::from_bits(self.bits.saturating_sub(other.bits));
+            }
+
+            pub fn saturating_mul(self, other: Fixed<$t, FRAC>) -> Fixed<$t, FRAC> {
+                let product = self.bits as i128 * other.bits as i128;
+                return Self::saturate(divide(product, Self::SCALE as i128, Rounding::NearestEven));
+            }
+
+            // panics on a zero divisor like the integer one does
+            pub fn saturating_div(self, other: Fixed<$t, FRAC>) -> Fixed<$t, FRAC> {
+                if other.bits == 0 {
+                    panic!("fixed-point division by zero");
+                }
+                return Self::saturate(divide((self.bits as i128) << FRAC, other.bits as i128, Rounding::NearestEven));
+            }
+        }
+
+        // ----------- OPERATORS ------------
+
+        impl<const FRAC: u32> Add for Fixed<$t, FRAC> {
+            type Output = Fixed<$t, FRAC>;
+
+            fn add(self, other: Self) -> Self::Output {
+                return self.checked_add(other).expect("fixed-point overflow");
+            }
+        }
+
+        impl<const FRAC: u32> Sub for Fixed<$t, FRAC> {
+            type Output = Fixed<$t, FRAC>;
+
+            fn sub(self, other: Self) -> Self::Output {
+                return self.checked_sub(other).expect("fixed-point overflow");
+            }
+        }
+
+        impl<const FRAC: u32> Mul for Fixed<$t, FRAC> {
+            type Output = Fixed<$t, FRAC>;
+
+            fn mul(self, other: Self) -> Self::Output {
+                return self.checked_mul(other).expect("fixed-point overflow");
+            }
+        }
+
+        impl<const FRAC: u32> Div for Fixed<$t, FRAC> {
+            type Output = Fixed<$t, FRAC>;
+
+            fn div(self, other: Self) -> Self::Output {
+                if other.bits == 0 {
+                    panic!("fixed-point division by zero");
+                }
+                return self.checked_div(other).expect("fixed-point overflow");
+            }
+        }
+
+        impl<const FRAC: u32> Rem for Fixed<$t, FRAC> {
+            type Output = Fixed<$t, FRAC>;
+
+            fn rem(self, other: Self) -> Self::Output {
+                if other.bits == 0 {
+                    panic!("fixed-point division by zero");
+                }
+                return self.checked_rem(other).expect("fixed-point overflow");
+            }
+        }
+
+        impl<const FRAC: u32> Neg for Fixed<$t, FRAC> {
+            type Output = Fixed<$t, FRAC>;
+
+            fn neg(self) -> Self::Output {
+                return self.checked_neg().expect("fixed-point overflow");
+            }
+        }
+
+        impl<const FRAC: u32> AddAssign for Fixed<$t, FRAC> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<const FRAC: u32> SubAssign for Fixed<$t, FRAC> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<const FRAC: u32> MulAssign for Fixed<$t, FRAC> {
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        impl<const FRAC: u32> DivAssign for Fixed<$t, FRAC> {
+            fn div_assign(&mut self, other: Self) {
+                *self = *self / other;
+            }
+        }
+
+        impl<const FRAC: u32> RemAssign for Fixed<$t, FRAC> {
+            fn rem_assign(&mut self, other: Self) {
+                *self = *self % other;
+            }
+        }
+
+        impl<const FRAC: u32> Zero for Fixed<$t, FRAC> {
+            fn zero() -> Fixed<$t, FRAC> {
+                return Self::ZERO;
+            }
+
+            fn is_zero(&self) -> bool {
+                return self.bits == 0;
+            }
+        }
+
+        impl<const FRAC: u32> One for Fixed<$t, FRAC> {
+            fn one() -> Fixed<$t, FRAC> {
+                return Self::ONE;
+            }
+        }
+
+        impl<const FRAC: u32> Signed for Fixed<$t, FRAC> {
+            fn signum(self) -> Fixed<$t, FRAC> {
+                return Self::from_bits(self.bits.signum() * Self::SCALE);
+            }
+        }
+
+        // ----------- PARSING AND FORMATTING ------------
+
+        // "-12.375", "+3", ".5" or "7.", rounded to nearest even. The fraction
+        // digits are turned into bits by repeated doubling, so any number of
+        // them is rounded correctly.
+        impl<const FRAC: u32> FromStr for Fixed<$t, FRAC> {
+            type Err = ParseFixedError;
+
+            fn from_str(text: &str) -> Result<Fixed<$t, FRAC>, ParseFixedError> {
+                let (negative, unsigned) = match text.as_bytes().first() {
+                    Some(b'-') => (true, &text[1..]),
+                    Some(b'+') => (false, &text[1..]),
+                    _ => (false, text)
+                };
+                let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+                let all_digits = |part: &str| part.bytes().all(|c| c.is_ascii_digit());
+                if (integer.is_empty() && fraction.is_empty()) || !all_digits(integer) || !all_digits(fraction) {
+                    return Err(ParseFixedError::Invalid);
+                }
+
+                let mut magnitude: i128 = 0;
+                for digit in integer.bytes() {
+                    magnitude = magnitude * 10 + (digit - b'0') as i128;
+                    if magnitude > <$t>::MAX as i128 + 1 {
+                        return Err(ParseFixedError::OutOfRange);
+                    }
+                }
+
+                let mut digits: Vec<u8> = fraction.bytes().map(|c| c - b'0').collect();
+                for _ in 0..FRAC {
+                    let mut carry = 0;
+                    for digit in digits.iter_mut().rev() {
+                        let doubled = *digit * 2 + carry;
+                        *digit = doubled % 10;
+                        carry = doubled / 10;
+                    }
+                    magnitude = magnitude * 2 + carry as i128;
+                }
+                // what is left is the part of an ulp that was cut off
+                let half = match digits.iter().position(|digit| *digit != 0) {
+                    None => None,
+                    Some(_) if digits[0] > 5 => Some(std::cmp::Ordering::Greater),
+                    Some(_) if digits[0] < 5 => Some(std::cmp::Ordering::Less),
+                    Some(_) => Some(if digits[1..].iter().any(|digit| *digit != 0) {
+                        std::cmp::Ordering::Greater
+                    } else {
+                        std::cmp::Ordering::Equal
+                    })
+                };
+                if half == Some(std::cmp::Ordering::Greater)
+                    || (half == Some(std::cmp::Ordering::Equal) && magnitude % 2 == 1) {
+                    magnitude += 1;
+                }
+                return Self::narrow(if negative { -magnitude } else { magnitude }).ok_or(ParseFixedError::OutOfRange);
+            }
+        }
+
+        // The exact decimal value, a binary fraction never has more than FRAC
+        // digits. {:.N} rounds to nearest even, sign and width work as for
+        // integers.
+        impl<const FRAC: u32> Display for Fixed<$t, FRAC> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                let magnitude = (self.bits as i128).unsigned_abs();
+                let mut integer = magnitude >> FRAC;
+                let mut remainder = magnitude & ((1 << FRAC) - 1);
+                let mut digits: Vec<u8> = vec![];
+                while remainder != 0 {
+                    remainder *= 10;
+                    digits.push((remainder >> FRAC) as u8);
+                    remainder &= (1 << FRAC) - 1;
+                }
+
+                if let Some(precision) = f.precision() {
+                    if precision < digits.len() {
+                        let rest = &digits[precision..];
+                        let last_odd = match precision {
+                            0 => integer % 2 == 1,
+                            _ => digits[precision - 1] % 2 == 1
+                        };
+                        let up = rest[0] > 5 || (rest[0] == 5 && (rest[1..].iter().any(|digit| *digit != 0) || last_odd));
+                        digits.truncate(precision);
+                        if up {
+                            let mut carry = true;
+                            for digit in digits.iter_mut().rev() {
+                                *digit += 1;
+                                carry = *digit == 10;
+                                if !carry {
+                                    break;
+                                }
+                                *digit = 0;
+                            }
+                            if carry {
+                                integer += 1;
+                            }
+                        }
+                    }
+                    digits.resize(precision, 0);
+                }
+
+                let mut text = integer.to_string();
+                if !digits.is_empty() {
+                    text.push('.');
+                    text.extend(digits.iter().map(|digit| (b'0' + digit) as char));
+                }
+                return f.pad_integral(self.bits >= 0, "", &text);
+            }
+        }
+    )*};
+}
+
+impl_fixed!(i32, i64);
+
+impl<const FRAC: u32> From<Fixed<i32, FRAC>> for Fixed<i64, FRAC> {
+    fn from(value: Fixed<i32, FRAC>) -> Fixed<i64, FRAC> {
+        return Fixed::<i64, FRAC>::from_bits(value.to_bits() as i64);
+    }
+}
+
+impl<const FRAC: u32> TryFrom<Fixed<i64, FRAC>> for Fixed<i32, FRAC> {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: Fixed<i64, FRAC>) -> Result<Fixed<i32, FRAC>, Self::Error> {
+        return Ok(Fixed::<i32, FRAC>::from_bits(i32::try_from(value.to_bits())?));
+    }
+}
+
+// Fixed-point components divide like floats, by Smith's algorithm
+impl<S, const FRAC: u32> ComplexDiv for Fixed<S, FRAC> where Fixed<S, FRAC>: Signed {
+    fn checked_div(lhs: &Complex<Fixed<S, FRAC>>, rhs: &Complex<Fixed<S, FRAC>>) -> Result<Complex<Fixed<S, FRAC>>, ComplexError> {
+        return smith_div(lhs, rhs);
+    }
+}
+
+#[cfg(test)]
+type Q16 = Fixed<i32, 16>;
+
+#[test]
+fn test_fixed_arithmetic() {
+    let q = |value: f64| Q16::from_f64(value).unwrap();
+    assert_eq!(Q16::ONE.to_bits(), 65536);
+    assert_eq!(q(1.5) + q(2.25), q(3.75));
+    assert_eq!(q(1.5) - q(2.25), q(-0.75));
+    assert_eq!(q(1.5) * q(-2.25), q(-3.375));
+    assert_eq!(q(3.375) / q(1.5), q(2.25));
+    assert_eq!(q(7.5) % q(2.0), q(1.5));
+    assert_eq!(-q(7.5) % q(2.0), q(-1.5));
+    assert_eq!(Q16::from_int(3), Some(q(3.0)));
+    assert_eq!(Q16::from_int(40000), None);
+
+    let mut value = q(1.0);
+    value += q(0.5);
+    value -= q(0.25);
+    value *= q(4.0);
+    value /= q(2.0);
+    value %= q(2.0);
+    assert_eq!(value, q(0.5));
+
+    // 1/3 is rounded to the nearest bits, from both sides
+    let third = Q16::ONE / q(3.0);
+    assert_eq!(third.to_bits(), 21845);
+    assert_eq!((-Q16::ONE / q(3.0)).to_bits(), -21845);
+    assert_eq!(Q16::ONE.checked_div_with(q(3.0), Rounding::Ceil).unwrap().to_bits(), 21846);
+    assert_eq!((-Q16::ONE).checked_div_with(q(3.0), Rounding::Floor).unwrap().to_bits(), -21846);
+    assert_eq!(Q16::EPSILON.checked_mul_with(q(0.5), Rounding::NearestEven), Some(Q16::ZERO));
+    assert_eq!(Q16::EPSILON.checked_mul_with(q(0.5), Rounding::Nearest), Some(Q16::EPSILON));
+    assert_eq!(Q16::EPSILON.checked_mul_with(q(0.5), Rounding::TowardZero), Some(Q16::ZERO));
+
+    assert_eq!(Q16::MAX.checked_add(Q16::EPSILON), None);
+    assert_eq!(Q16::MIN.checked_sub(Q16::EPSILON), None);
+    assert_eq!(Q16::MIN.checked_neg(), None);
+    assert_eq!(q(200.0).checked_mul(q(200.0)), None);
+    assert_eq!(q(1.0).checked_div(Q16::ZERO), None);
+    assert_eq!(Q16::MAX.saturating_add(Q16::ONE), Q16::MAX);
+    assert_eq!(Q16::MIN.saturating_sub(Q16::ONE), Q16::MIN);
+    assert_eq!(q(200.0).saturating_mul(q(-200.0)), Q16::MIN);
+    assert_eq!(q(200.0).saturating_div(q(0.001)), Q16::MAX);
+    assert_eq!(q(-3.0).saturating_mul(q(0.5)), q(-1.5));
+
+    assert_eq!(Signed::abs(q(-2.5)), q(2.5));
+    assert_eq!(Signed::signum(q(-2.5)), q(-1.0));
+
+    // i64 storage keeps products of i64 values exact up to the rounding
+    type Q32 = Fixed<i64, 32>;
+    let big = Q32::from_int(1 << 30).unwrap();
+    assert_eq!(big * Q32::from_f64(0.5).unwrap(), Q32::from_int(1 << 29).unwrap());
+    assert_eq!(big.checked_mul(Q32::from_int(2).unwrap()), None);
+    assert_eq!(Fixed::<i64, 16>::from(q(-1.25)), Fixed::<i64, 16>::from_f64(-1.25).unwrap());
+    assert_eq!(Q16::try_from(Fixed::<i64, 16>::from_int(3).unwrap()), Ok(q(3.0)));
+    assert!(Q16::try_from(Fixed::<i64, 16>::from_int(1 << 20).unwrap()).is_err());
+}
+
+#[test]
+#[should_panic(expected = "fixed-point overflow")]
+fn test_fixed_overflow() {
+    let _ = Q16::MAX + Q16::EPSILON;
+}
+
+#[test]
+fn test_fixed_conversions() {
+    assert_eq!(Q16::from_f64(0.1).unwrap().to_bits(), 6554);
+    assert_eq!(Q16::from_f64_with(0.1, Rounding::Floor).unwrap().to_bits(), 6553);
+    assert_eq!(Q16::from_f64(1.5 / 65536.0).unwrap().to_bits(), 2);
+    assert_eq!(Q16::from_f64(2.5 / 65536.0).unwrap().to_bits(), 2);
+    assert_eq!(Q16::from_f64_with(2.5 / 65536.0, Rounding::Nearest).unwrap().to_bits(), 3);
+    assert_eq!(Q16::from_f64(32767.99999), Some(Q16::MAX));
+    assert_eq!(Q16::from_f64(32768.0), None);
+    assert_eq!(Q16::from_f64(-32768.0), Some(Q16::MIN));
+    assert_eq!(Q16::from_f64(f64::NAN), None);
+    assert_eq!(Q16::from_f64(-1.75).unwrap().to_f64(), -1.75);
+
+    let value = Q16::from_f64(-2.5).unwrap();
+    assert_eq!(value.to_int(Rounding::Floor), -3);
+    assert_eq!(value.to_int(Rounding::Ceil), -2);
+    assert_eq!(value.to_int(Rounding::TowardZero), -2);
+    assert_eq!(value.to_int(Rounding::Nearest), -3);
+    assert_eq!(value.to_int(Rounding::NearestEven), -2);
+
+    let fine: Fixed<i32, 20> = value.rescale(Rounding::NearestEven).unwrap();
+    assert_eq!(fine.to_f64(), -2.5);
+    let coarse: Option<Fixed<i32, 0>> = value.rescale(Rounding::Floor);
+    assert_eq!(coarse.unwrap().to_bits(), -3);
+    assert_eq!(Q16::MAX.rescale::<20>(Rounding::NearestEven), None);
+}
+
+#[test]
+fn test_fixed_parse_format() {
+    let q = |value: f64| Q16::from_f64(value).unwrap();
+    assert_eq!("1.5".parse(), Ok(q(1.5)));
+    assert_eq!("-12.375".parse(), Ok(q(-12.375)));
+    assert_eq!("+3".parse(), Ok(q(3.0)));
+    assert_eq!(".5".parse(), Ok(q(0.5)));
+    assert_eq!("7.".parse(), Ok(q(7.0)));
+    assert_eq!("0.1".parse::<Q16>().unwrap().to_bits(), 6554);
+    assert_eq!("-32768".parse(), Ok(Q16::MIN));
+    assert_eq!("32768".parse::<Q16>(), Err(ParseFixedError::OutOfRange));
+    assert_eq!("99999999999999999999999".parse::<Q16>(), Err(ParseFixedError::OutOfRange));
+    for text in ["", ".", "-", "1.2.3", "1e5", "abc", " 1"] {
+        assert_eq!(text.parse::<Q16>(), Err(ParseFixedError::Invalid));
+    }
+    // exactly half an ulp goes to even, anything above it up
+    assert_eq!("0.00000762939453125".parse::<Q16>().unwrap().to_bits(), 0);
+    assert_eq!("0.000007629394531250000000000000001".parse::<Q16>().unwrap().to_bits(), 1);
+    assert_eq!("0.00002288818359375".parse::<Q16>().unwrap().to_bits(), 2);
+
+    assert_eq!(format!("{}", q(-12.375)), "-12.375");
+    assert_eq!(format!("{}", q(3.0)), "3");
+    assert_eq!(format!("{}", Q16::EPSILON), "0.0000152587890625");
+    assert_eq!(format!("{}", Q16::MIN), "-32768");
+    assert_eq!(format!("{:.2}", q(-12.375)), "-12.38");
+    assert_eq!(format!("{:.2}", q(12.125)), "12.12");
+    assert_eq!(format!("{:.0}", q(2.5)), "2");
+    assert_eq!(format!("{:.0}", q(3.5)), "4");
+    assert_eq!(format!("{:.1}", q(9.96875)), "10.0");
+    assert_eq!(format!("{:.4}", q(1.5)), "1.5000");
+    assert_eq!(format!("{:+}", q(1.5)), "+1.5");
+    assert_eq!(format!("{:>8}", q(-1.5)), "    -1.5");
+    assert_eq!(format!("{:08.2}", q(-1.5)), "-0001.50");
+
+    // Display is exact, so every value parses back to itself
+    use crate::prng::{Rng, Xoshiro256};
+    let mut rng = Xoshiro256::new(47);
+    for _ in 0..1000 {
+        let value = Q16::from_bits(rng.next_u32() as i32);
+        assert_eq!(format!("{}", value).parse(), Ok(value));
+        let value = Fixed::<i64, 40>::from_bits(rng.next_u64() as i64);
+        assert_eq!(format!("{}", value).parse(), Ok(value));
+    }
+}
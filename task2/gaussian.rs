@@ -0,0 +1,300 @@
+use std::ops::{Rem, RemAssign};
+use crate::complex::{Complex, ComplexError};
+
+// Gaussian integers a + bi. Division rounds to the nearest gaussian integer,
+// see impl_gaussian_div, so together with the remainder it is euclidean.
+
+const ZERO: Complex<i64> = Complex { re: 0, im: 0 };
+const ONE: Complex<i64> = Complex { re: 1, im: 0 };
+const UNITS: [Complex<i64>; 4] = [
+    Complex { re: 1, im: 0 }, Complex { re: 0, im: 1 }, Complex { re: -1, im: 0 }, Complex { re: 0, im: -1 }
+];
+
+impl Complex<i64> {
+    // a² + b², the size that gets smaller in every euclidean step. It is at
+    // most 2^127, for i64::MIN + i64::MIN i, which only fits a u128.
+    pub fn norm_sqr(&self) -> u128 {
+        let (re, im) = (self.re.unsigned_abs() as u128, self.im.unsigned_abs() as u128);
+        return re * re + im * im;
+    }
+
+    // self = quotient * other + remainder with N(remainder) <= N(other) / 2
+    pub fn div_rem(&self, other: &Complex<i64>) -> Result<(Complex<i64>, Complex<i64>), ComplexError> {
+        let quotient = self.checked_div(other)?;
+        return Ok((quotient, *self - quotient * *other));
+    }
+
+    pub fn is_unit(&self) -> bool {
+        return self.norm_sqr() == 1;
+    }
+
+    // The associate in the first quadrant: real part positive, imaginary part
+    // not negative. Zero stays zero.
+    pub fn normalize(&self) -> Complex<i64> {
+        for unit in UNITS {
+            let associate = *self * unit;
+            if associate.re > 0 && associate.im >= 0 {
+                return associate;
+            }
+        }
+        return ZERO;
+    }
+
+    // normalized, so gcd(0, 0) is 0 and the gcd of coprime numbers is 1
+    pub fn gcd(&self, other: &Complex<i64>) -> Complex<i64> {
+        let (mut a, mut b) = (*self, *other);
+        while b != ZERO {
+            let remainder = a % b;
+            a = b;
+            b = remainder;
+        }
+        return a.normalize();
+    }
+
+    pub fn divides(&self, other: &Complex<i64>) -> bool {
+        return *self != ZERO && (*other % *self) == ZERO;
+    }
+
+    // a + bi with a, b both non-zero is prime exactly when a² + b² is a prime,
+    // on the axes it is prime when |a| or |b| is a prime of the form 4k + 3.
+    // Any i64 parts work, the primality test is Miller-Rabin.
+    pub fn is_gaussian_prime(&self) -> bool {
+        if self.re == 0 || self.im == 0 {
+            let value = (self.re as i128 + self.im as i128).unsigned_abs();
+            return value % 4 == 3 && is_prime(value);
+        }
+        return is_prime(self.norm_sqr());
+    }
+
+    // Returns the unit and the normalized primes with their exponents ordered by
+    // norm, such that unit * Π prime^exponent == self. Trial division on the
+    // norm, so it is meant for numbers of moderate size. Zero has no factorization.
+    pub fn factorize(&self) -> Option<(Complex<i64>, Vec<(Complex<i64>, u32)>)> {
+        if *self == ZERO {
+            return None;
+        }
+
+        let mut rest = *self;
+        let mut factors = vec![];
+        for p in prime_factors(self.norm_sqr()) {
+            let candidates = if p == 2 {
+                vec![Complex { re: 1, im: 1 }]
+            } else if p % 4 == 3 {
+                vec![Complex { re: p as i64, im: 0 }]
+            } else {
+                // p splits into a conjugate pair, found as gcd(p, k + i) with k² ≡ -1
+                let k = sqrt_minus_one(p);
+                let prime = Complex { re: p as i64, im: 0 }.gcd(&Complex { re: k as i64, im: 1 });
+                vec![prime, Complex { re: prime.re, im: -prime.im }.normalize()]
+            };
+            for prime in candidates {
+                let mut exponent = 0;
+                while prime.divides(&rest) {
+                    rest = rest / prime;
+                    exponent += 1;
+                }
+                if exponent > 0 {
+                    factors.push((prime, exponent));
+                }
+            }
+        }
+        factors.sort_by_key(|(prime, _)| (prime.norm_sqr(), prime.re, prime.im));
+        return Some((rest, factors));
+    }
+}
+
+impl Rem for Complex<i64> {
+    type Output = Complex<i64>;
+
+    fn rem(self, other: Complex<i64>) -> Complex<i64> {
+        return match self.div_rem(&other) {
+            Ok((_, remainder)) => remainder,
+            Err(error) => panic!("{}", error)
+        };
+    }
+}
+
+impl RemAssign for Complex<i64> {
+    fn rem_assign(&mut self, other: Complex<i64>) {
+        *self = *self % other;
+    }
+}
+
+// ----------- RATIONAL PRIMES ------------
+
+// Distinct prime factors in increasing order
+fn prime_factors(mut n: u128) -> Vec<u128> {
+    let mut factors = vec![];
+    let mut d = 2;
+    while d * d <= n {
+        if n % d == 0 {
+            factors.push(d);
+            while n % d == 0 {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    return factors;
+}
+
+// The norm of an i64 gaussian integer is at most 2^127, so the modulus fits
+// a u128 but the product does not; double and add keeps everything below 2^128
+fn mul_mod(mut a: u128, mut b: u128, modulus: u128) -> u128 {
+    let mut result = 0;
+    a %= modulus;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+    return result;
+}
+
+fn pow_mod(mut base: u128, mut exponent: u128, modulus: u128) -> u128 {
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    return result;
+}
+
+// Miller-Rabin with the primes up to 37 as bases. No composite below
+// 3.3 * 10^24 passes all of them, larger norms up to 2^127 are strong
+// probable primes to these twelve bases.
+fn is_prime(n: u128) -> bool {
+    const BASES: [u128; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'bases: for a in BASES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    return true;
+}
+
+// k with k² ≡ -1 (mod p) for a prime p ≡ 1 (mod 4): c^((p-1)/4) for any
+// quadratic non-residue c
+fn sqrt_minus_one(p: u128) -> u128 {
+    let mut c = 2;
+    while pow_mod(c, (p - 1) / 2, p) != p - 1 {
+        c += 1;
+    }
+    return pow_mod(c, (p - 1) / 4, p);
+}
+
+#[test]
+fn test_euclidean_division() {
+    let c = |re: i64, im: i64| Complex { re, im };
+    assert_eq!(c(8, 0) / c(3, 0), c(3, 0)); // 2.67 is closer to 3
+    assert_eq!(c(7, 0) / c(2, 0), c(4, 0));
+    assert_eq!(c(-7, 0) / c(2, 0), c(-3, 0));
+    assert_eq!(c(4, 9) / c(3, 0), c(1, 3));
+    assert_eq!(c(27, 23) / c(8, 1), c(4, 2)); // 3.72 + 2.4i
+    assert_eq!(c(27, 23) % c(8, 1), c(-3, 3));
+    assert_eq!(c(1, 8).div_rem(&c(2, 1)), Ok((c(2, 3), ZERO)));
+    assert_eq!(c(1, 1).div_rem(&ZERO), Err(ComplexError::DivisionByZero));
+
+    let mut value = c(27, 23);
+    value %= c(8, 1);
+    assert_eq!(value, c(-3, 3));
+
+    // the remainder is always smaller than the divisor
+    use crate::prng::Pcg32;
+    let mut rng = Pcg32::new(43, 0);
+    for _ in 0..10000 {
+        let a: Complex<i64> = Complex::random(&mut rng, -1_000_000..1_000_000);
+        let b: Complex<i64> = Complex::random(&mut rng, -1000..1000);
+        if b == ZERO {
+            continue;
+        }
+        let (quotient, remainder) = a.div_rem(&b).unwrap();
+        assert_eq!(quotient * b + remainder, a);
+        assert!(2 * remainder.norm_sqr() <= b.norm_sqr());
+    }
+
+    let big = c(i64::MAX, i64::MIN + 1);
+    assert_eq!(big / big, ONE);
+    assert_eq!(big % c(1, 1), ZERO);
+}
+
+#[test]
+fn test_gcd() {
+    let c = |re: i64, im: i64| Complex { re, im };
+    assert_eq!(c(0, 0).normalize(), ZERO);
+    assert_eq!(c(-3, -2).normalize(), c(3, 2));
+    assert_eq!(c(2, -3).normalize(), c(3, 2));
+    assert_eq!(c(0, -5).normalize(), c(5, 0));
+    assert!(c(0, -1).is_unit() && !c(1, 1).is_unit());
+
+    assert_eq!(c(5, 0).gcd(&c(3, 4)), c(2, 1)); // 3 + 4i = (2 + i)², 5 = (2 + i)(2 - i)
+    assert_eq!(c(4, 0).gcd(&c(6, 0)), c(2, 0));
+    assert_eq!(c(7, 0).gcd(&c(3, 0)), ONE);
+    assert_eq!(c(0, 0).gcd(&c(0, -2)), c(2, 0));
+    assert_eq!(ZERO.gcd(&ZERO), ZERO);
+
+    let (a, b, d) = (c(11, 3), c(1, 8), c(2, 1));
+    let g = (a * d).gcd(&(b * d));
+    assert!(d.divides(&g));
+    assert!(g.divides(&(a * d)) && g.divides(&(b * d)));
+}
+
+#[test]
+fn test_primes() {
+    let c = |re: i64, im: i64| Complex { re, im };
+    let primes = [c(1, 1), c(2, 1), c(1, 2), c(3, 0), c(0, 7), c(-11, 0), c(4, 1), c(5, 2)];
+    let composites = [c(0, 0), c(1, 0), c(0, -1), c(2, 0), c(5, 0), c(13, 0), c(3, 4), c(2, 2)];
+    assert!(primes.iter().all(|p| p.is_gaussian_prime()));
+    assert!(composites.iter().all(|n| !n.is_gaussian_prime()));
+
+    // 2 = -i(1 + i)²
+    assert_eq!(c(2, 0).factorize(), Some((c(0, -1), vec![(c(1, 1), 2)])));
+    // 5 = (1 + 2i)(2 + i) up to a unit
+    let (unit, factors) = c(5, 0).factorize().unwrap();
+    assert_eq!(factors, vec![(c(1, 2), 1), (c(2, 1), 1)]);
+    assert_eq!(unit * c(1, 2) * c(2, 1), c(5, 0));
+    assert_eq!(c(0, -1).factorize(), Some((c(0, -1), vec![])));
+    assert_eq!(ZERO.factorize(), None);
+
+    for n in [c(360, 0), c(3, 4), c(-17, 19), c(1000, 999), c(97, 0), c(0, 1 << 20), c(600851475, 143)] {
+        let (unit, factors) = n.factorize().unwrap();
+        assert!(unit.is_unit());
+        let mut product = unit;
+        for (prime, exponent) in &factors {
+            assert!(prime.is_gaussian_prime());
+            assert_eq!(prime.normalize(), *prime);
+            for _ in 0..*exponent {
+                product = product * *prime;
+            }
+        }
+        assert_eq!(product, n);
+    }
+}
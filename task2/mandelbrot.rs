@@ -0,0 +1,362 @@
+// Mandelbrot and Julia renderer, writes binary PPM or PGM files:
+// mandelbrot [--julia C] [--view X,Y,WIDTH,HEIGHT] [--size WxH] [--iterations N] [--threads N] FILE
+mod num;
+mod approx;
+#[path = "../common/prng.rs"]
+mod prng;
+mod complex;
+mod rectangle;
+
+use std::fs::File;
+use std::fmt::Display;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+use std::time::Instant;
+use crate::complex::Complex;
+use crate::rectangle::{Rectangle, Rect};
+
+// A large bailout radius makes the smooth iteration count continuous
+const BAILOUT: f64 = 256.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fractal {
+    // z -> z² + c with z starting at 0 and c the pixel
+    Mandelbrot,
+    // z -> z² + c with z starting at the pixel and a fixed c
+    Julia(Complex<f64>)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Grey,
+    Colour
+}
+
+// The viewport is a rectangle in the complex plane, x and width along the
+// real axis. Row 0 of the image is its top edge.
+struct Settings {
+    fractal: Fractal,
+    viewport: Rectangle<f64>,
+    width: usize,
+    height: usize,
+    iterations: u32,
+    threads: usize
+}
+
+// ----------- ITERATION ------------
+
+// Smooth iteration count n + 1 - log2(ln |z|) of the first z outside the
+// bailout radius, None when the orbit stays bounded for all iterations
+fn escape_time(fractal: Fractal, point: Complex<f64>, iterations: u32) -> Option<f64> {
+    let (mut z, c) = match fractal {
+        Fractal::Mandelbrot => (Complex { re: 0.0, im: 0.0 }, point),
+        Fractal::Julia(c) => (point, c)
+    };
+    for n in 0..iterations {
+        z = z * z + c;
+        let norm_sqr = z.norm_sqr();
+        if norm_sqr > BAILOUT * BAILOUT {
+            // ln |z| = ln |z|² / 2
+            return Some(n as f64 + 1.0 - (norm_sqr.ln() / 2.0).log2());
+        }
+    }
+    return None;
+}
+
+// The complex number at the center of a pixel
+fn pixel_point(settings: &Settings, column: usize, row: usize) -> Complex<f64> {
+    let viewport = &settings.viewport;
+    return Complex {
+        re: viewport.left() + (column as f64 + 0.5) / settings.width as f64 * viewport.width(),
+        im: viewport.top() - (row as f64 + 0.5) / settings.height as f64 * viewport.height()
+    };
+}
+
+// No more threads than rows, and at least one
+fn thread_count(settings: &Settings) -> usize {
+    return settings.threads.clamp(1, settings.height.max(1));
+}
+
+// Rows go round robin to the threads, neighbouring rows take about as long,
+// so every thread gets its share of the slow parts inside the set
+fn render(settings: &Settings) -> Vec<Option<f64>> {
+    let mut values = vec![None; settings.width * settings.height];
+    if values.is_empty() {
+        return values;
+    }
+
+    let threads = thread_count(settings);
+    let mut work: Vec<Vec<(usize, &mut [Option<f64>])>> = (0..threads).map(|_| Vec::new()).collect();
+    for (row, pixels) in values.chunks_mut(settings.width).enumerate() {
+        work[row % threads].push((row, pixels));
+    }
+
+    std::thread::scope(|scope| {
+        for rows in work {
+            scope.spawn(move || {
+                for (row, pixels) in rows {
+                    for (column, value) in pixels.iter_mut().enumerate() {
+                        let point = pixel_point(settings, column, row);
+                        *value = escape_time(settings.fractal, point, settings.iterations);
+                    }
+                }
+            });
+        }
+    });
+    return values;
+}
+
+// ----------- COLOURING ------------
+
+// Points inside the set are black, outside the brightness falls off with
+// the square root of the escape time so the filaments stay visible
+fn grey(value: Option<f64>, iterations: u32) -> u8 {
+    return match value {
+        Some(value) => {
+            let t = (value.max(0.0) / iterations as f64).sqrt().min(1.0);
+            (255.0 * (1.0 - t)).round() as u8
+        },
+        None => 0
+    };
+}
+
+// A cosine palette, the phases are shifted per channel and it cycles every
+// 64 iterations, so deep zooms keep their contrast
+fn colour(value: Option<f64>) -> [u8; 3] {
+    let value = match value {
+        Some(value) => value.max(0.0),
+        None => return [0, 0, 0]
+    };
+    let t = value / 64.0 * 2.0 * std::f64::consts::PI;
+    let channel = |phase: f64| (127.5 * (1.0 - (t + phase).cos())).round() as u8;
+    return [channel(0.0), channel(0.6), channel(1.2)];
+}
+
+// ----------- OUTPUT ------------
+
+// Binary netpbm: P5 is one grey byte per pixel, P6 three RGB bytes
+fn write_image<W: Write>(out: &mut W, settings: &Settings, values: &[Option<f64>],
+                         format: Format) -> std::io::Result<()> {
+    let (magic, bytes) = match format {
+        Format::Grey => ("P5", values.iter().map(|value| grey(*value, settings.iterations)).collect::<Vec<u8>>()),
+        Format::Colour => ("P6", values.iter().flat_map(|value| colour(*value)).collect())
+    };
+    write!(out, "{}\n{} {}\n255\n", magic, settings.width, settings.height)?;
+    out.write_all(&bytes)?;
+    return out.flush();
+}
+
+// ----------- COMMAND LINE ------------
+
+fn parse_list<T: FromStr>(text: &str, count: usize, separator: char) -> Result<Vec<T>, String>
+where T::Err: Display {
+    let values: Vec<T> = text.split(separator)
+        .map(|part| part.trim().parse::<T>())
+        .collect::<Result<_, _>>()
+        .map_err(|error| format!("{}: {}", text, error))?;
+    if values.len() != count {
+        return Err(format!("{}: expected {} values", text, count));
+    }
+    return Ok(values);
+}
+
+// Returns the settings, the output path and its format, which follows the
+// extension: .pgm is grey, anything else colour
+fn parse_args(args: &[String]) -> Result<(Settings, String, Format), String> {
+    let mut settings = Settings {
+        fractal: Fractal::Mandelbrot,
+        viewport: Rectangle::new(-2.5, -1.25, 3.5, 2.5),
+        width: 1400,
+        height: 1000,
+        iterations: 500,
+        threads: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+    };
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            if path.replace(arg.clone()).is_some() {
+                return Err(format!("unexpected argument {}", arg));
+            }
+            continue;
+        }
+
+        let value = args.next().ok_or(format!("{} needs a value", arg))?;
+        match arg.as_str() {
+            "--julia" => {
+                let c = value.parse::<Complex<f64>>().map_err(|error| format!("{}: {}", value, error))?;
+                settings.fractal = Fractal::Julia(c);
+            },
+            "--view" => {
+                let view = parse_list::<f64>(value, 4, ',')?;
+                if !(view[2] > 0.0 && view[3] > 0.0) {
+                    return Err(format!("{}: the view needs a positive size", value));
+                }
+                settings.viewport = Rectangle::new(view[0], view[1], view[2], view[3]);
+            },
+            "--size" => {
+                let size = parse_list::<usize>(value, 2, 'x')?;
+                if size[0] == 0 || size[1] == 0 {
+                    return Err(format!("{}: the image needs a positive size", value));
+                }
+                // render keeps an Option<f64> per pixel, more than the 3 bytes of P6
+                let bytes = size[0].checked_mul(size[1])
+                    .and_then(|pixels| pixels.checked_mul(std::mem::size_of::<Option<f64>>()));
+                if !bytes.is_some_and(|bytes| bytes <= isize::MAX as usize) {
+                    return Err(format!("{}: too many pixels", value));
+                }
+                settings.width = size[0];
+                settings.height = size[1];
+            },
+            "--iterations" => settings.iterations = value.parse().map_err(|_| format!("{}: not a count", value))?,
+            "--threads" => settings.threads = value.parse().map_err(|_| format!("{}: not a count", value))?,
+            _ => return Err(format!("unknown option {}", arg))
+        }
+    }
+
+    let path = path.ok_or("missing output file".to_string())?;
+    let format = if path.to_lowercase().ends_with(".pgm") { Format::Grey } else { Format::Colour };
+    return Ok((settings, path, format));
+}
+
+#[test]
+fn test_escape_time() {
+    // the origin and -1 have bounded orbits, 1 goes 1, 2, 5, 26, 677
+    assert_eq!(escape_time(Fractal::Mandelbrot, Complex { re: 0.0, im: 0.0 }, 1000), None);
+    assert_eq!(escape_time(Fractal::Mandelbrot, Complex { re: -1.0, im: 0.0 }, 1000), None);
+    let one = escape_time(Fractal::Mandelbrot, Complex { re: 1.0, im: 0.0 }, 1000).unwrap();
+    assert!(one > 2.0 && one < 3.0);
+    assert_eq!(escape_time(Fractal::Mandelbrot, Complex { re: 1.0, im: 0.0 }, 3), None);
+
+    // the count is continuous, close points outside the set get close values
+    let a = escape_time(Fractal::Mandelbrot, Complex { re: 0.5, im: 0.5 }, 1000).unwrap();
+    let b = escape_time(Fractal::Mandelbrot, Complex { re: 0.5 + 1e-9, im: 0.5 }, 1000).unwrap();
+    assert!((a - b).abs() < 1e-5);
+
+    // the Julia set of c = 0 is the unit circle
+    let julia = Fractal::Julia(Complex { re: 0.0, im: 0.0 });
+    assert_eq!(escape_time(julia, Complex { re: 0.6, im: -0.7 }, 1000), None);
+    assert!(escape_time(julia, Complex { re: 0.8, im: 0.7 }, 1000).is_some());
+}
+
+#[test]
+fn test_render() {
+    let mut settings = Settings {
+        fractal: Fractal::Mandelbrot,
+        viewport: Rectangle::new(-2.0, -1.0, 3.0, 2.0),
+        width: 30,
+        height: 20,
+        iterations: 200,
+        threads: 1
+    };
+    assert_eq!(pixel_point(&settings, 0, 0), Complex { re: -1.95, im: 0.95 });
+    assert!((pixel_point(&settings, 29, 19) - Complex { re: 0.95, im: -0.95 }).norm() < 1e-12);
+
+    let single = render(&settings);
+    assert_eq!(single.len(), 600);
+    assert!(single.iter().any(|value| value.is_none()) && single.iter().any(|value| value.is_some()));
+    // the set is symmetric about the real axis
+    for row in 0..20 {
+        for column in 0..30 {
+            assert_eq!(single[row * 30 + column].is_none(), single[(19 - row) * 30 + column].is_none());
+        }
+    }
+
+    for threads in [2, 3, 7, 64] {
+        settings.threads = threads;
+        assert_eq!(render(&settings), single);
+    }
+
+    assert_eq!(thread_count(&settings), 20);
+    settings.threads = 0;
+    assert_eq!(thread_count(&settings), 1);
+
+    settings.width = 0;
+    assert!(render(&settings).is_empty());
+}
+
+#[test]
+fn test_write_image() {
+    let settings = Settings {
+        fractal: Fractal::Mandelbrot,
+        viewport: Rectangle::new(-2.0, -1.0, 3.0, 2.0),
+        width: 2,
+        height: 1,
+        iterations: 100,
+        threads: 1
+    };
+    let values = [None, Some(0.0)];
+
+    let mut out = Vec::new();
+    write_image(&mut out, &settings, &values, Format::Grey).unwrap();
+    assert_eq!(out, b"P5\n2 1\n255\n\x00\xff");
+
+    let mut out = Vec::new();
+    write_image(&mut out, &settings, &values, Format::Colour).unwrap();
+    assert_eq!(&out[..11], b"P6\n2 1\n255\n");
+    assert_eq!(&out[11..], &[0, 0, 0, 0, 22, 81]);
+
+    assert_eq!(grey(Some(100.0), 100), 0);
+    assert_eq!(grey(Some(25.0), 100), 128);
+    assert_eq!(colour(Some(64.0)), colour(Some(0.0)));
+}
+
+#[test]
+fn test_parse_args() {
+    let args = |text: &str| text.split_whitespace().map(String::from).collect::<Vec<String>>();
+
+    let (settings, path, format) = parse_args(&args("out.ppm")).unwrap();
+    assert_eq!((path.as_str(), format), ("out.ppm", Format::Colour));
+    assert_eq!(settings.fractal, Fractal::Mandelbrot);
+
+    let (settings, path, format) = parse_args(
+        &args("--julia -0.8+0.156i --view -1.5,-1,3,2 --size 300x200 --iterations 50 --threads 3 julia.PGM")).unwrap();
+    assert_eq!((path.as_str(), format), ("julia.PGM", Format::Grey));
+    assert_eq!(settings.fractal, Fractal::Julia(Complex { re: -0.8, im: 0.156 }));
+    assert_eq!(settings.viewport, Rectangle::new(-1.5, -1.0, 3.0, 2.0));
+    assert_eq!((settings.width, settings.height, settings.iterations, settings.threads), (300, 200, 50, 3));
+
+    assert!(parse_args(&args("")).is_err());
+    assert!(parse_args(&args("a.ppm b.ppm")).is_err());
+    assert!(parse_args(&args("--size 300 out.ppm")).is_err());
+    assert!(parse_args(&args("--size 1.7x-3 out.ppm")).is_err());
+    assert!(parse_args(&args("--size 0x5 out.ppm")).is_err());
+    assert!(parse_args(&args("--size 1e30x1e30 out.ppm")).is_err());
+    assert_eq!(parse_args(&args("--size 4294967296x4294967296 out.ppm")).err(),
+               Some("4294967296x4294967296: too many pixels".to_string()));
+    assert_eq!(parse_args(&args("--size 4294967296x4294967295 out.ppm")).err(),
+               Some("4294967296x4294967295: too many pixels".to_string()));
+    assert!(parse_args(&args("--size 65536x65536 out.ppm")).is_ok());
+    assert!(parse_args(&args("--view 0,0,-1,1 out.ppm")).is_err());
+    assert!(parse_args(&args("--julia 1+ out.ppm")).is_err());
+    assert!(parse_args(&args("--depth 3 out.ppm")).is_err());
+    assert!(parse_args(&args("out.ppm --iterations")).is_err());
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (settings, path, format) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!("usage: mandelbrot [--julia C] [--view X,Y,WIDTH,HEIGHT] [--size WxH] \
+                       [--iterations N] [--threads N] FILE");
+            std::process::exit(2);
+        }
+    };
+
+    let start = Instant::now();
+    let values = render(&settings);
+    let elapsed = start.elapsed();
+    let threads = thread_count(&settings);
+    println!("{}x{} pixels in {:.3} s, {:.2} Mpixel/s on {} thread{}", settings.width, settings.height,
+             elapsed.as_secs_f64(), values.len() as f64 / elapsed.as_secs_f64() / 1e6,
+             threads, if threads == 1 { "" } else { "s" });
+
+    let written = File::create(&path).and_then(|file| write_image(&mut BufWriter::new(file), &settings, &values, format));
+    if let Err(error) = written {
+        eprintln!("{}: {}", path, error);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,468 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub, Mul, Index, IndexMut};
+use crate::Scalar;
+use crate::approx::{ApproxEq, Difference};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatrixError {
+    ShapeMismatch { left: (usize, usize), right: (usize, usize) },
+    NotSquare { rows: usize, cols: usize },
+    Singular
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            MatrixError::ShapeMismatch { left, right } =>
+                write!(f, "shape mismatch: {}x{} and {}x{}", left.0, left.1, right.0, right.1),
+            MatrixError::NotSquare { rows, cols } => write!(f, "matrix is not square: {}x{}", rows, cols),
+            MatrixError::Singular => write!(f, "matrix is singular")
+        };
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+// Dense matrix stored row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>
+}
+
+impl<T: Copy> Matrix<T> {
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::ShapeMismatch { left: (rows, cols), right: (data.len(), 1) });
+        }
+        return Ok(Matrix { rows, cols, data });
+    }
+
+    // All rows must have the same length
+    pub fn from_rows(rows: &[Vec<T>]) -> Result<Matrix<T>, MatrixError> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for row in rows {
+            if row.len() != cols {
+                return Err(MatrixError::ShapeMismatch { left: (rows.len(), cols), right: (1, row.len()) });
+            }
+            data.extend_from_slice(row);
+        }
+        return Ok(Matrix { rows: rows.len(), cols, data });
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Matrix<T> {
+        return Matrix { rows, cols, data: vec![value; rows * cols] };
+    }
+
+    pub fn rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn cols(&self) -> usize {
+        return self.cols;
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        return (self.rows, self.cols);
+    }
+
+    pub fn is_square(&self) -> bool {
+        return self.rows == self.cols;
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        return &self.data[row * self.cols..(row + 1) * self.cols];
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for col in 0..self.cols {
+            for row in 0..self.rows {
+                data.push(self[(row, col)]);
+            }
+        }
+        return Matrix { rows: self.cols, cols: self.rows, data };
+    }
+
+    fn zip_with(&self, other: &Matrix<T>, op: impl Fn(T, T) -> T) -> Result<Matrix<T>, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::ShapeMismatch { left: self.shape(), right: other.shape() });
+        }
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| op(*a, *b)).collect();
+        return Ok(Matrix { rows: self.rows, cols: self.cols, data });
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Matrix<T> {
+    pub fn checked_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        return self.zip_with(other, |a, b| a + b);
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Matrix<T> {
+    pub fn checked_sub(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        return self.zip_with(other, |a, b| a - b);
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        return Matrix::filled(rows, cols, T::zero());
+    }
+
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut matrix = Matrix::zeros(size, size);
+        for i in 0..size {
+            matrix[(i, i)] = T::one();
+        }
+        return matrix;
+    }
+
+    pub fn conjugate_transpose(&self) -> Matrix<T> {
+        let mut matrix = self.transpose();
+        matrix.data.iter_mut().for_each(|value| *value = value.conj());
+        return matrix;
+    }
+
+    pub fn checked_mul(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::ShapeMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut result = Matrix::zeros(self.rows, other.cols);
+        for row in 0..self.rows {
+            for k in 0..self.cols {
+                let value = self[(row, k)];
+                for col in 0..other.cols {
+                    result[(row, col)] = result[(row, col)] + value * other[(k, col)];
+                }
+            }
+        }
+        return Ok(result);
+    }
+
+    pub fn lu(&self) -> Result<Lu<T>, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare { rows: self.rows, cols: self.cols });
+        }
+
+        let size = self.rows;
+        let mut factors = self.clone();
+        let mut permutation: Vec<usize> = (0..size).collect();
+        let mut odd = false;
+        let mut singular = false;
+        // pivots this small compared to the entries are rounding noise
+        let largest = self.data.iter().map(|value| value.magnitude()).fold(0.0, f64::max);
+        let tolerance = largest * size as f64 * f64::EPSILON;
+
+        for col in 0..size {
+            let pivot = (col..size)
+                .max_by(|a, b| factors[(*a, col)].magnitude().total_cmp(&factors[(*b, col)].magnitude()))
+                .unwrap();
+            if factors[(pivot, col)].magnitude() <= tolerance {
+                singular = true;
+                continue;
+            }
+            if pivot != col {
+                for k in 0..size {
+                    factors.data.swap(pivot * size + k, col * size + k);
+                }
+                permutation.swap(pivot, col);
+                odd = !odd;
+            }
+
+            for row in col + 1..size {
+                let factor = factors[(row, col)] / factors[(col, col)];
+                factors[(row, col)] = factor;
+                for k in col + 1..size {
+                    factors[(row, k)] = factors[(row, k)] - factor * factors[(col, k)];
+                }
+            }
+        }
+        return Ok(Lu { factors, permutation, odd, singular });
+    }
+
+    pub fn determinant(&self) -> Result<T, MatrixError> {
+        return Ok(self.lu()?.determinant());
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        return self.lu()?.inverse();
+    }
+
+    // Solves self * x = b, every column of b is its own right-hand side
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        return self.lu()?.solve(b);
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(row < self.rows && col < self.cols, "index out of bounds");
+        return &self.data[row * self.cols + col];
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(row < self.rows && col < self.cols, "index out of bounds");
+        return &mut self.data[row * self.cols + col];
+    }
+}
+
+// matrices of different shapes are never close
+impl<T: ApproxEq> ApproxEq for Matrix<T> {
+    fn difference(&self, other: &Matrix<T>) -> Difference {
+        if (self.rows, self.cols) != (other.rows, other.cols) {
+            return Difference::INFINITE;
+        }
+        return self.data.iter().zip(&other.data)
+            .fold(Difference::ZERO, |difference, (a, b)| difference.max(a.difference(b)));
+    }
+}
+
+// The operators panic on mismatched shapes, the checked_ methods return the error
+
+fn expect<T>(result: Result<Matrix<T>, MatrixError>) -> Matrix<T> {
+    return match result {
+        Ok(matrix) => matrix,
+        Err(error) => panic!("{}", error)
+    };
+}
+
+impl<'a, T: Copy + Add<Output = T>> Add for &'a Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, other: &'a Matrix<T>) -> Matrix<T> {
+        return expect(self.checked_add(other));
+    }
+}
+
+impl<'a, T: Copy + Sub<Output = T>> Sub for &'a Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, other: &'a Matrix<T>) -> Matrix<T> {
+        return expect(self.checked_sub(other));
+    }
+}
+
+impl<'a, T: Scalar> Mul for &'a Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: &'a Matrix<T>) -> Matrix<T> {
+        return expect(self.checked_mul(other));
+    }
+}
+
+impl<T: Copy + Add<Output = T>> Add for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, other: Matrix<T>) -> Matrix<T> {
+        return &self + &other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> Sub for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, other: Matrix<T>) -> Matrix<T> {
+        return &self - &other;
+    }
+}
+
+impl<T: Scalar> Mul for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Matrix<T>) -> Matrix<T> {
+        return &self * &other;
+    }
+}
+
+// ----------- LU DECOMPOSITION ------------
+
+// P * A = L * U with partial pivoting. L has a unit diagonal and is stored
+// below the diagonal of factors, U on and above it. Row i of P * A is row
+// permutation[i] of A.
+pub struct Lu<T> {
+    factors: Matrix<T>,
+    permutation: Vec<usize>,
+    odd: bool,
+    singular: bool
+}
+
+impl<T: Scalar> Lu<T> {
+    pub fn is_singular(&self) -> bool {
+        return self.singular;
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        return &self.permutation;
+    }
+
+    pub fn lower(&self) -> Matrix<T> {
+        let size = self.factors.rows;
+        let mut lower = Matrix::identity(size);
+        for row in 0..size {
+            for col in 0..row {
+                lower[(row, col)] = self.factors[(row, col)];
+            }
+        }
+        return lower;
+    }
+
+    pub fn upper(&self) -> Matrix<T> {
+        let size = self.factors.rows;
+        let mut upper = Matrix::zeros(size, size);
+        for row in 0..size {
+            for col in row..size {
+                upper[(row, col)] = self.factors[(row, col)];
+            }
+        }
+        return upper;
+    }
+
+    pub fn determinant(&self) -> T {
+        if self.singular {
+            return T::zero();
+        }
+        let mut determinant = if self.odd { -T::one() } else { T::one() };
+        for i in 0..self.factors.rows {
+            determinant = determinant * self.factors[(i, i)];
+        }
+        return determinant;
+    }
+
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let size = self.factors.rows;
+        if b.rows != size {
+            return Err(MatrixError::ShapeMismatch { left: self.factors.shape(), right: b.shape() });
+        }
+        if self.singular {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut x = Matrix::zeros(size, b.cols);
+        for col in 0..b.cols {
+            // forward substitution with L, then back substitution with U
+            for row in 0..size {
+                let mut value = b[(self.permutation[row], col)];
+                for k in 0..row {
+                    value = value - self.factors[(row, k)] * x[(k, col)];
+                }
+                x[(row, col)] = value;
+            }
+            for row in (0..size).rev() {
+                let mut value = x[(row, col)];
+                for k in row + 1..size {
+                    value = value - self.factors[(row, k)] * x[(k, col)];
+                }
+                x[(row, col)] = value / self.factors[(row, row)];
+            }
+        }
+        return Ok(x);
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        return self.solve(&Matrix::identity(self.factors.rows));
+    }
+}
+
+#[test]
+fn test_basic_operations() {
+    let a = Matrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+    let b = Matrix::from_vec(3, 2, vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0]).unwrap();
+    assert_eq!(a.shape(), (2, 3));
+    assert_eq!(a[(1, 2)], 6.0);
+    assert_eq!(a.row(1), &[4.0, 5.0, 6.0]);
+    assert_eq!(&a * &b, Matrix::from_rows(&[vec![58.0, 64.0], vec![139.0, 154.0]]).unwrap());
+    assert_eq!(a.transpose(), Matrix::from_rows(&[vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]]).unwrap());
+    assert_eq!(&a + &a, Matrix::from_rows(&[vec![2.0, 4.0, 6.0], vec![8.0, 10.0, 12.0]]).unwrap());
+    assert_eq!(a.clone() - a.clone(), Matrix::zeros(2, 3));
+    assert_eq!(a.clone() * Matrix::identity(3), a);
+    assert_eq!(Matrix::<f64>::identity(2) * Matrix::identity(2), Matrix::identity(2));
+    assert_eq!(Matrix::filled(1, 2, 5).shape(), (1, 2));
+
+    assert_eq!(a.checked_add(&b), Err(MatrixError::ShapeMismatch { left: (2, 3), right: (3, 2) }));
+    assert_eq!(a.checked_sub(&b), Err(MatrixError::ShapeMismatch { left: (2, 3), right: (3, 2) }));
+    assert_eq!(a.checked_mul(&a), Err(MatrixError::ShapeMismatch { left: (2, 3), right: (2, 3) }));
+    assert_eq!(Matrix::from_vec(2, 2, vec![1.0]), Err(MatrixError::ShapeMismatch { left: (2, 2), right: (1, 1) }));
+    assert_eq!(Matrix::from_rows(&[vec![1.0], vec![1.0, 2.0]]), Err(MatrixError::ShapeMismatch { left: (2, 1), right: (1, 2) }));
+    assert_eq!(a.determinant(), Err(MatrixError::NotSquare { rows: 2, cols: 3 }));
+    assert_eq!(Matrix::<f64>::from_rows(&[]).unwrap().shape(), (0, 0));
+}
+
+#[test]
+#[should_panic(expected = "shape mismatch: 2x2 and 3x3")]
+fn test_shape_panic() {
+    let _ = Matrix::<f64>::identity(2) * Matrix::identity(3);
+}
+
+#[test]
+fn test_decomposition() {
+    let a = Matrix::from_rows(&[vec![2.0f64, 1.0, 1.0], vec![4.0, -6.0, 0.0], vec![-2.0, 7.0, 2.0]]).unwrap();
+    assert_approx_eq!(a.determinant().unwrap(), -16.0, abs = 1e-12);
+    let inverse = a.inverse().unwrap();
+    assert_approx_eq!(&a * &inverse, Matrix::identity(3), abs = 1e-12);
+
+    let b = Matrix::from_vec(3, 1, vec![5.0, -2.0, 9.0]).unwrap();
+    let x = a.solve(&b).unwrap();
+    assert_approx_eq!(x, Matrix::from_vec(3, 1, vec![1.0, 1.0, 2.0]).unwrap(), abs = 1e-12);
+    assert_approx_ne!(x, Matrix::from_vec(1, 3, vec![1.0, 1.0, 2.0]).unwrap(), abs = 1e300);
+
+    // a zero in the top left corner needs a row swap
+    let swap = Matrix::from_rows(&[vec![0.0, 1.0], vec![1.0, 0.0]]).unwrap();
+    assert_eq!(swap.determinant(), Ok(-1.0));
+    assert_eq!(swap.inverse(), Ok(swap.clone()));
+
+    let singular = Matrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]]).unwrap();
+    assert!(singular.lu().unwrap().is_singular());
+    assert_eq!(singular.determinant(), Ok(0.0));
+    assert_eq!(singular.inverse(), Err(MatrixError::Singular));
+    assert_eq!(a.solve(&Matrix::zeros(2, 1)), Err(MatrixError::ShapeMismatch { left: (3, 3), right: (2, 1) }));
+    assert_eq!(Matrix::<f64>::identity(0).determinant(), Ok(1.0));
+}
+
+#[test]
+fn test_complex_matrices() {
+    use crate::Complex;
+    use crate::prng::{Rng, Xoshiro256};
+    let c = |re: f64, im: f64| Complex { re, im };
+
+    let a = Matrix::from_rows(&[vec![c(1.0, 1.0), c(2.0, 0.0)], vec![c(0.0, -1.0), c(3.0, 2.0)]]).unwrap();
+    assert_eq!(a.conjugate_transpose(), Matrix::from_rows(&[vec![c(1.0, -1.0), c(0.0, 1.0)], vec![c(2.0, 0.0), c(3.0, -2.0)]]).unwrap());
+    // (1+i)(3+2i) - 2(-i) = 1 + 7i
+    assert_approx_eq!(a.determinant().unwrap(), c(1.0, 7.0), abs = 1e-12);
+
+    // A^H A is hermitian
+    let gram = &a.conjugate_transpose() * &a;
+    assert_eq!(gram.conjugate_transpose(), gram);
+
+    let mut rng = Xoshiro256::new(41);
+    for size in [1, 2, 3, 5, 8, 13] {
+        let random = |rng: &mut Xoshiro256, rows, cols| {
+            let data = (0..rows * cols).map(|_| c(rng.range(-1.0..1.0), rng.range(-1.0..1.0))).collect();
+            return Matrix::from_vec(rows, cols, data).unwrap();
+        };
+        let a = random(&mut rng, size, size);
+        let x = random(&mut rng, size, 2);
+        let b = &a * &x;
+
+        let lu = a.lu().unwrap();
+        let permuted = Matrix::from_rows(&lu.permutation().iter().map(|row| a.row(*row).to_vec()).collect::<Vec<_>>()).unwrap();
+        assert_approx_eq!(&lu.lower() * &lu.upper(), permuted, abs = 1e-12);
+        assert_approx_eq!(a.solve(&b).unwrap(), x, abs = 1e-9, "size {}", size);
+        assert_approx_eq!(&a * &a.inverse().unwrap(), Matrix::identity(size), abs = 1e-9, "size {}", size);
+
+        // det(AB) = det(A) det(B)
+        let other = random(&mut rng, size, size);
+        let product = (&a * &other).determinant().unwrap();
+        let expected = a.determinant().unwrap() * other.determinant().unwrap();
+        assert!((product - expected).norm() < 1e-9 * (1.0 + expected.norm()));
+    }
+
+    let single = Matrix::from_rows(&[vec![1.0f32, 2.0], vec![3.0, 4.0]]).unwrap();
+    assert_approx_eq!(single.determinant().unwrap(), -2.0, abs = 1e-6);
+}
@@ -0,0 +1,425 @@
+use std::fmt::Debug;
+use std::ops::Add;
+use crate::rectangle::{Point, Rectangle};
+
+// ----------- BIT INTERLEAVING ------------
+
+// Moves bit i of value to bit 2i
+fn spread_2d(value: u32) -> u64 {
+    let mut x = value as u64;
+    x = (x | (x << 16)) & 0x0000FFFF0000FFFF;
+    x = (x | (x << 8)) & 0x00FF00FF00FF00FF;
+    x = (x | (x << 4)) & 0x0F0F0F0F0F0F0F0F;
+    x = (x | (x << 2)) & 0x3333333333333333;
+    x = (x | (x << 1)) & 0x5555555555555555;
+    return x;
+}
+
+fn compact_2d(key: u64) -> u32 {
+    let mut x = key & 0x5555555555555555;
+    x = (x | (x >> 1)) & 0x3333333333333333;
+    x = (x | (x >> 2)) & 0x0F0F0F0F0F0F0F0F;
+    x = (x | (x >> 4)) & 0x00FF00FF00FF00FF;
+    x = (x | (x >> 8)) & 0x0000FFFF0000FFFF;
+    x = (x | (x >> 16)) & 0x00000000FFFFFFFF;
+    return x as u32;
+}
+
+// Moves bit i of a 16 bit value to bit 3i
+fn spread_3d(value: u16) -> u64 {
+    let mut x = value as u64;
+    x = (x | (x << 16)) & 0x0000FF0000FF;
+    x = (x | (x << 8)) & 0x00F00F00F00F;
+    x = (x | (x << 4)) & 0x0C30C30C30C3;
+    x = (x | (x << 2)) & 0x249249249249;
+    return x;
+}
+
+fn compact_3d(key: u64) -> u16 {
+    let mut x = key & 0x249249249249;
+    x = (x | (x >> 2)) & 0x0C30C30C30C3;
+    x = (x | (x >> 4)) & 0x00F00F00F00F;
+    x = (x | (x >> 8)) & 0x0000FF0000FF;
+    x = (x | (x >> 16)) & 0x00000000FFFF;
+    return x as u16;
+}
+
+// x takes the lowest bit of every group, then y (and z)
+pub trait Morton: Copy + Ord + Debug + Add<Output = Self> + Into<u32> {
+    type Key2: Copy + Ord + Debug;
+    type Key3: Copy + Ord + Debug;
+    const BITS: u32;
+
+    fn encode_2d(x: Self, y: Self) -> Self::Key2;
+    fn decode_2d(key: Self::Key2) -> (Self, Self);
+    fn encode_3d(x: Self, y: Self, z: Self) -> Self::Key3;
+    fn decode_3d(key: Self::Key3) -> (Self, Self, Self);
+    fn key2_from_u64(key: u64) -> Self::Key2;
+}
+
+impl Morton for u16 {
+    type Key2 = u32;
+    type Key3 = u64;
+    const BITS: u32 = 16;
+
+    fn encode_2d(x: u16, y: u16) -> u32 {
+        return (spread_2d(x as u32) | (spread_2d(y as u32) << 1)) as u32;
+    }
+
+    fn decode_2d(key: u32) -> (u16, u16) {
+        return (compact_2d(key as u64) as u16, compact_2d(key as u64 >> 1) as u16);
+    }
+
+    fn encode_3d(x: u16, y: u16, z: u16) -> u64 {
+        return spread_3d(x) | (spread_3d(y) << 1) | (spread_3d(z) << 2);
+    }
+
+    fn decode_3d(key: u64) -> (u16, u16, u16) {
+        return (compact_3d(key), compact_3d(key >> 1), compact_3d(key >> 2));
+    }
+
+    fn key2_from_u64(key: u64) -> u32 {
+        return key as u32;
+    }
+}
+
+impl Morton for u32 {
+    type Key2 = u64;
+    type Key3 = u128;
+    const BITS: u32 = 32;
+
+    fn encode_2d(x: u32, y: u32) -> u64 {
+        return spread_2d(x) | (spread_2d(y) << 1);
+    }
+
+    fn decode_2d(key: u64) -> (u32, u32) {
+        return (compact_2d(key), compact_2d(key >> 1));
+    }
+
+    // the low and high halves are interleaved separately:
+    // bit 16 + i of a coordinate lands on bit 48 + 3i of the key
+    fn encode_3d(x: u32, y: u32, z: u32) -> u128 {
+        let low = u16::encode_3d(x as u16, y as u16, z as u16) as u128;
+        let high = u16::encode_3d((x >> 16) as u16, (y >> 16) as u16, (z >> 16) as u16) as u128;
+        return low | (high << 48);
+    }
+
+    fn decode_3d(key: u128) -> (u32, u32, u32) {
+        let low = u16::decode_3d(key as u64 & 0xFFFFFFFFFFFF);
+        let high = u16::decode_3d((key >> 48) as u64);
+        return (
+            low.0 as u32 | (high.0 as u32) << 16,
+            low.1 as u32 | (high.1 as u32) << 16,
+            low.2 as u32 | (high.2 as u32) << 16
+        );
+    }
+
+    fn key2_from_u64(key: u64) -> u64 {
+        return key;
+    }
+}
+
+pub fn encode_2d<T: Morton>(x: T, y: T) -> T::Key2 {
+    return T::encode_2d(x, y);
+}
+
+pub fn decode_2d<T: Morton>(key: T::Key2) -> (T, T) {
+    return T::decode_2d(key);
+}
+
+pub fn encode_3d<T: Morton>(x: T, y: T, z: T) -> T::Key3 {
+    return T::encode_3d(x, y, z);
+}
+
+pub fn decode_3d<T: Morton>(key: T::Key3) -> (T, T, T) {
+    return T::decode_3d(key);
+}
+
+// ----------- POINTS AND RECTANGLES ------------
+
+impl<T: Morton> Point<T> {
+    pub fn morton_key(&self) -> T::Key2 {
+        return T::encode_2d(self.x, self.y);
+    }
+}
+
+pub fn sort_by_morton<T: Morton>(points: &mut [Point<T>]) {
+    points.sort_by_key(|point| point.morton_key());
+}
+
+// Right and top edge of the rectangle, added in u64 so rectangles reaching
+// past the largest coordinate are cut at the border instead of overflowing
+fn far_corner<T: Morton>(rect: &Rectangle<T>) -> (u64, u64) {
+    let largest = (1u64 << T::BITS) - 1;
+    let right = (rect.x.into() as u64 + rect.width.into() as u64).min(largest);
+    let top = (rect.y.into() as u64 + rect.height.into() as u64).min(largest);
+    return (right, top);
+}
+
+// Smallest and largest key of the points inside the rectangle (borders included).
+// Every point of the rectangle falls into this range, but the range usually
+// contains keys of outside points as well, see key_ranges for an exact cover.
+pub fn key_range<T: Morton>(rect: &Rectangle<T>) -> (T::Key2, T::Key2) {
+    let (right, top) = far_corner(rect);
+    return (
+        T::encode_2d(rect.x, rect.y),
+        T::key2_from_u64(spread_2d(right as u32) | (spread_2d(top as u32) << 1))
+    );
+}
+
+// Sorted, disjoint key ranges whose union is exactly the set of keys of the
+// points inside the rectangle, found by walking the implicit quadtree.
+pub fn key_ranges<T: Morton>(rect: &Rectangle<T>) -> Vec<(T::Key2, T::Key2)> {
+    let left = rect.x.into() as u64;
+    let bottom = rect.y.into() as u64;
+    let (right, top) = far_corner(rect);
+
+    let mut ranges: Vec<(u64, u64)> = vec![];
+    // quadtree cells as (x, y, log2 of the side), pushed in reverse z-order
+    let mut stack = vec![(0u64, 0u64, T::BITS)];
+    while let Some((x, y, level)) = stack.pop() {
+        let last = (1u64 << level) - 1;
+        if x > right || x + last < left || y > top || y + last < bottom {
+            continue;
+        }
+        if left <= x && x + last <= right && bottom <= y && y + last <= top {
+            let first = spread_2d(x as u32) | (spread_2d(y as u32) << 1);
+            let end = first + ((1u128 << (2 * level)) - 1) as u64;
+            match ranges.last_mut() {
+                Some(range) if range.1 + 1 == first => range.1 = end,
+                _ => ranges.push((first, end))
+            }
+            continue;
+        }
+        let half = 1u64 << (level - 1);
+        stack.push((x + half, y + half, level - 1));
+        stack.push((x, y + half, level - 1));
+        stack.push((x + half, y, level - 1));
+        stack.push((x, y, level - 1));
+    }
+
+    return ranges.into_iter()
+        .map(|(first, last)| (T::key2_from_u64(first), T::key2_from_u64(last)))
+        .collect();
+}
+
+// ----------- HILBERT CURVE ------------
+
+fn hilbert_rotate(side: u64, x: &mut u64, y: &mut u64, rx: u64, ry: u64) {
+    if ry == 0 {
+        if rx == 1 {
+            *x = side - 1 - *x;
+            *y = side - 1 - *y;
+        }
+        (*x, *y) = (*y, *x);
+    }
+}
+
+// Position of (x, y) along the Hilbert curve filling a 2^order x 2^order grid,
+// the coordinates are u32 so the order is at most 32
+pub fn hilbert_index(order: u32, x: u32, y: u32) -> u64 {
+    assert!(order <= 32, "hilbert curve order {} is above 32", order);
+    let side = 1u64 << order;
+    assert!((x as u64) < side && (y as u64) < side, "({}, {}) is outside the {}x{} hilbert grid", x, y, side, side);
+    let (mut x, mut y) = (x as u64, y as u64);
+    let mut index = 0;
+    let mut s = side / 2;
+    while s > 0 {
+        let rx = ((x & s) > 0) as u64;
+        let ry = ((y & s) > 0) as u64;
+        index += s * s * ((3 * rx) ^ ry);
+        hilbert_rotate(side, &mut x, &mut y, rx, ry);
+        s /= 2;
+    }
+    return index;
+}
+
+pub fn hilbert_point(order: u32, index: u64) -> (u32, u32) {
+    assert!(order <= 32, "hilbert curve order {} is above 32", order);
+    let side = 1u64 << order;
+    assert!(order == 32 || index < side * side, "hilbert index {} is past the {}x{} grid", index, side, side);
+    let (mut x, mut y) = (0u64, 0u64);
+    let mut t = index;
+    let mut s = 1;
+    while s < side {
+        let rx = 1 & (t / 2);
+        let ry = 1 & (t ^ rx);
+        hilbert_rotate(s, &mut x, &mut y, rx, ry);
+        x += s * rx;
+        y += s * ry;
+        t /= 4;
+        s *= 2;
+    }
+    return (x as u32, y as u32);
+}
+
+fn interleave_naive(coords: &[u32], bits: u32) -> u128 {
+    let mut key: u128 = 0;
+    for bit in 0..bits {
+        for (i, coord) in coords.iter().enumerate() {
+            key |= (((*coord >> bit) & 1) as u128) << (bit as usize * coords.len() + i);
+        }
+    }
+    return key;
+}
+
+fn sample_coords(count: usize) -> Vec<u32> {
+    let mut state: u32 = 0x9E3779B9;
+    let mut coords = vec![0, 1, u16::MAX as u32, u32::MAX];
+    for _ in 0..count {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        coords.push(state);
+    }
+    return coords;
+}
+
+#[test]
+fn test_morton_encoding() {
+    assert_eq!(encode_2d(0b11u16, 0b00u16), 0b0101);
+    assert_eq!(encode_2d(0b00u16, 0b11u16), 0b1010);
+    assert_eq!(encode_3d(1u16, 1u16, 1u16), 0b111);
+    assert_eq!(encode_3d(0u32, 0u32, 1u32 << 31), 1u128 << 95);
+    assert_eq!(decode_2d::<u32>(u64::MAX), (u32::MAX, u32::MAX));
+
+    let coords = sample_coords(300);
+    for triple in coords.windows(3) {
+        let (x, y, z) = (triple[0], triple[1], triple[2]);
+        let key2 = encode_2d(x, y);
+        assert_eq!(key2 as u128, interleave_naive(&[x, y], 32));
+        assert_eq!(decode_2d(key2), (x, y));
+
+        let key3 = encode_3d(x, y, z);
+        assert_eq!(key3, interleave_naive(&[x, y, z], 32));
+        assert_eq!(decode_3d(key3), (x, y, z));
+
+        let (x, y, z) = (x as u16, y as u16, z as u16);
+        let key2 = encode_2d(x, y);
+        assert_eq!(key2 as u128, interleave_naive(&[x as u32, y as u32], 16));
+        assert_eq!(decode_2d(key2), (x, y));
+
+        let key3 = encode_3d(x, y, z);
+        assert_eq!(key3 as u128, interleave_naive(&[x as u32, y as u32, z as u32], 16));
+        assert_eq!(decode_3d(key3), (x, y, z));
+    }
+}
+
+#[test]
+fn test_morton_rectangles() {
+    let rects: [Rectangle<u16>; 5] = [
+        Rectangle {x: 0, y: 0, width: 63, height: 63},
+        Rectangle {x: 5, y: 9, width: 20, height: 13},
+        Rectangle {x: 16, y: 16, width: 15, height: 15},
+        Rectangle {x: 33, y: 2, width: 0, height: 40},
+        Rectangle {x: 7, y: 7, width: 0, height: 0}
+    ];
+
+    for rect in &rects {
+        let (low, high) = key_range(rect);
+        let ranges = key_ranges(rect);
+        for pair in ranges.windows(2) {
+            assert!(pair[0].1 + 1 < pair[1].0);
+        }
+
+        for x in 0..80u16 {
+            for y in 0..80u16 {
+                let point = Point {x, y};
+                let key = point.morton_key();
+                let inside = rect.x <= x && x <= rect.x + rect.width
+                    && rect.y <= y && y <= rect.y + rect.height;
+                if inside {
+                    assert!(low <= key && key <= high);
+                }
+                let covered = ranges.iter().any(|(first, last)| *first <= key && key <= *last);
+                assert_eq!(covered, inside, "{:?} {:?}", rect, point);
+            }
+        }
+    }
+
+    assert_eq!(key_ranges(&Rectangle {x: 0u16, y: 0, width: 3, height: 3}), vec![(0, 15)]);
+    assert_eq!(key_ranges(&Rectangle {x: 0u32, y: 0, width: u32::MAX, height: u32::MAX}),
+               vec![(0, u64::MAX)]);
+    assert_eq!(key_ranges(&Rectangle {x: 0u16, y: 0, width: 1, height: 2}), vec![(0, 3), (8, 9)]);
+}
+
+#[test]
+fn test_morton_border_rectangles() {
+    // the far corner is past the largest coordinate, the rectangle is cut there
+    let rect = Rectangle {x: u16::MAX - 1, y: u16::MAX, width: 10, height: u16::MAX};
+    assert_eq!(key_range(&rect), (encode_2d(u16::MAX - 1, u16::MAX), u32::MAX));
+    assert_eq!(key_ranges(&rect), vec![(encode_2d(u16::MAX - 1, u16::MAX), u32::MAX)]);
+
+    let rect = Rectangle {x: u32::MAX - 1, y: u32::MAX - 1, width: 5, height: u32::MAX};
+    assert_eq!(key_range(&rect), (u64::MAX - 3, u64::MAX));
+    assert_eq!(key_ranges(&rect), vec![(u64::MAX - 3, u64::MAX)]);
+}
+
+#[test]
+fn test_morton_sort() {
+    let mut points: Vec<Point<u32>> = vec![];
+    for y in (0..4).rev() {
+        for x in (0..4).rev() {
+            points.push(Point {x, y});
+        }
+    }
+    sort_by_morton(&mut points);
+
+    assert_eq!(points[..6], [
+        Point {x: 0, y: 0}, Point {x: 1, y: 0}, Point {x: 0, y: 1},
+        Point {x: 1, y: 1}, Point {x: 2, y: 0}, Point {x: 3, y: 0}
+    ]);
+    for pair in points.windows(2) {
+        assert!(pair[0].morton_key() < pair[1].morton_key());
+    }
+}
+
+#[test]
+fn test_hilbert() {
+    assert_eq!(hilbert_index(1, 0, 0), 0);
+    assert_eq!(hilbert_index(1, 0, 1), 1);
+    assert_eq!(hilbert_index(1, 1, 1), 2);
+    assert_eq!(hilbert_index(1, 1, 0), 3);
+
+    // consecutive cells along the curve are neighbours
+    for order in 1..7 {
+        let mut prev = hilbert_point(order, 0);
+        assert_eq!(prev, (0, 0));
+        for index in 1..1u64 << (2 * order) {
+            let point = hilbert_point(order, index);
+            assert_eq!(hilbert_index(order, point.0, point.1), index);
+            assert_eq!(prev.0.abs_diff(point.0) + prev.1.abs_diff(point.1), 1);
+            prev = point;
+        }
+        assert_eq!(prev, ((1 << order) - 1, 0));
+    }
+
+    for pair in sample_coords(100).windows(2) {
+        let index = hilbert_index(32, pair[0], pair[1]);
+        assert_eq!(hilbert_point(32, index), (pair[0], pair[1]));
+    }
+}
+
+#[test]
+#[should_panic(expected = "hilbert curve order 33 is above 32")]
+fn test_hilbert_order_limit() {
+    let _ = hilbert_index(33, 0, 0);
+}
+
+#[test]
+#[should_panic(expected = "hilbert curve order 64 is above 32")]
+fn test_hilbert_point_order_limit() {
+    let _ = hilbert_point(64, 0);
+}
+
+#[test]
+#[should_panic(expected = "(7, 0) is outside the 4x4 hilbert grid")]
+fn test_hilbert_coordinate_limit() {
+    let _ = hilbert_index(2, 7, 0);
+}
+
+#[test]
+#[should_panic(expected = "hilbert index 16 is past the 4x4 grid")]
+fn test_hilbert_point_index_limit() {
+    let _ = hilbert_point(2, 16);
+}
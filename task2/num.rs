@@ -0,0 +1,290 @@
+// Numeric traits shared by the task2 crates, so a bound reads T: Num instead
+// of the operator list. Every crate only uses part of it.
+#![allow(dead_code)]
+
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+
+pub trait Zero: Sized + Add<Output = Self> {
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
+}
+
+pub trait One: Sized + Mul<Output = Self> {
+    fn one() -> Self;
+}
+
+// Any type with zero, one and the four operations is a Num, new number types
+// only implement Zero and One
+pub trait Num: Copy + PartialEq + Zero + One + Sub<Output = Self> + Div<Output = Self> {
+    fn two() -> Self {
+        return Self::one() + Self::one();
+    }
+}
+
+impl<T: Copy + PartialEq + Zero + One + Sub<Output = T> + Div<Output = T>> Num for T {}
+
+pub trait Signed: Num + Neg<Output = Self> + PartialOrd {
+    fn abs(self) -> Self {
+        return if self.is_negative() { -self } else { self };
+    }
+
+    fn signum(self) -> Self;
+
+    fn is_negative(self) -> bool {
+        return self < Self::zero();
+    }
+}
+
+// Every primitive integer but u128, the conversions go through i128 so exact
+// types like Ratio can be built on any of them, and u128 does not fit it
+pub trait Integer: Num + Ord + Rem<Output = Self> {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+
+    // Never negative, gcd(0, 0) is 0. Taken on the magnitudes, so MIN % -1
+    // is never formed; None when the gcd does not fit, which is only the case
+    // for gcd(MIN, 0) and gcd(MIN, MIN) of a signed type.
+    fn checked_gcd(self, other: Self) -> Option<Self> {
+        let (mut a, mut b) = (self.to_i128().unsigned_abs(), other.to_i128().unsigned_abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        return i128::try_from(a).ok().and_then(Self::from_i128);
+    }
+
+    // Panics where checked_gcd returns None
+    fn gcd(self, other: Self) -> Self {
+        return match self.checked_gcd(other) {
+            Some(gcd) => gcd,
+            None => panic!("gcd does not fit the integer type")
+        };
+    }
+}
+
+// The functions forward to the inherent ones of f32 and f64, generic code
+// gets its constants through from_f64
+pub trait Float: Signed {
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn nan() -> Self;
+    fn epsilon() -> Self;
+    fn pi() -> Self;
+    fn is_nan(self) -> bool;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, exponent: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl Zero for $t {
+            fn zero() -> $t {
+                return 0;
+            }
+
+            fn is_zero(&self) -> bool {
+                return *self == 0;
+            }
+        }
+
+        impl One for $t {
+            fn one() -> $t {
+                return 1;
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn to_i128(self) -> i128 {
+                return self as i128;
+            }
+
+            fn from_i128(value: i128) -> Option<$t> {
+                return <$t>::try_from(value).ok();
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed_int {
+    ($($t:ty),*) => {$(
+        impl Signed for $t {
+            fn abs(self) -> $t {
+                return <$t>::abs(self);
+            }
+
+            fn signum(self) -> $t {
+                return <$t>::signum(self);
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {$(
+        impl Zero for $t {
+            fn zero() -> $t {
+                return 0.0;
+            }
+
+            fn is_zero(&self) -> bool {
+                return *self == 0.0;
+            }
+        }
+
+        impl One for $t {
+            fn one() -> $t {
+                return 1.0;
+            }
+        }
+
+        impl Signed for $t {
+            fn abs(self) -> $t {
+                return <$t>::abs(self);
+            }
+
+            fn signum(self) -> $t {
+                return <$t>::signum(self);
+            }
+        }
+
+        impl Float for $t {
+            fn from_f64(value: f64) -> $t {
+                return value as $t;
+            }
+
+            fn to_f64(self) -> f64 {
+                return self as f64;
+            }
+
+            fn nan() -> $t {
+                return $t::NAN;
+            }
+
+            fn epsilon() -> $t {
+                return $t::EPSILON;
+            }
+
+            fn pi() -> $t {
+                return std::$t::consts::PI;
+            }
+
+            fn is_nan(self) -> bool {
+                return <$t>::is_nan(self);
+            }
+
+            fn sqrt(self) -> $t {
+                return <$t>::sqrt(self);
+            }
+
+            fn hypot(self, other: $t) -> $t {
+                return <$t>::hypot(self, other);
+            }
+
+            fn copysign(self, sign: $t) -> $t {
+                return <$t>::copysign(self, sign);
+            }
+
+            fn exp(self) -> $t {
+                return <$t>::exp(self);
+            }
+
+            fn ln(self) -> $t {
+                return <$t>::ln(self);
+            }
+
+            fn powf(self, exponent: $t) -> $t {
+                return <$t>::powf(self, exponent);
+            }
+
+            fn sin(self) -> $t {
+                return <$t>::sin(self);
+            }
+
+            fn cos(self) -> $t {
+                return <$t>::cos(self);
+            }
+
+            fn sin_cos(self) -> ($t, $t) {
+                return <$t>::sin_cos(self);
+            }
+
+            fn asin(self) -> $t {
+                return <$t>::asin(self);
+            }
+
+            fn acos(self) -> $t {
+                return <$t>::acos(self);
+            }
+
+            fn atan2(self, other: $t) -> $t {
+                return <$t>::atan2(self, other);
+            }
+
+            fn sinh(self) -> $t {
+                return <$t>::sinh(self);
+            }
+
+            fn cosh(self) -> $t {
+                return <$t>::cosh(self);
+            }
+        }
+    )*};
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+impl_signed_int!(i8, i16, i32, i64, i128, isize);
+impl_float!(f32, f64);
+
+#[test]
+fn test_num() {
+    fn half<T: Num>(value: T) -> T {
+        return value / T::two();
+    }
+
+    assert_eq!(half(9), 4);
+    assert_eq!(half(9u8), 4);
+    assert_eq!(half(9.0), 4.5);
+    assert!(0i64.is_zero() && !1u16.is_zero() && (-0.0f32).is_zero());
+    assert_eq!(<u128 as One>::one(), 1);
+
+    assert_eq!(Signed::abs(-3i32), 3);
+    assert_eq!(Signed::signum(-3i8), -1);
+    assert!(Signed::is_negative(-0.5f64) && !Signed::is_negative(0.0f32));
+
+    assert_eq!(12.gcd(-18), 6);
+    assert_eq!(0u8.gcd(7), 7);
+    assert_eq!(0i64.gcd(0), 0);
+    assert_eq!(i64::MIN.gcd(-1), 1);
+    assert_eq!(i64::MIN.checked_gcd(0), None);
+    assert_eq!(i128::MIN.checked_gcd(i128::MIN), None);
+    assert_eq!(i8::MIN.checked_gcd(96), Some(32));
+    assert_eq!(u64::MAX.checked_gcd(u64::MAX), Some(u64::MAX));
+    assert_eq!(<i8 as Integer>::from_i128(200), None);
+    assert_eq!(<u32 as Integer>::from_i128(200), Some(200));
+    assert_eq!((-5i16).to_i128(), -5);
+
+    fn hypotenuse<T: Float>(a: T, b: T) -> T {
+        return (a * a + b * b).sqrt();
+    }
+
+    assert_eq!(hypotenuse(3.0f32, 4.0), 5.0);
+    assert_eq!(hypotenuse(3.0f64, 4.0), 5.0);
+    assert_eq!(<f32 as Float>::from_f64(0.5).to_f64(), 0.5);
+    assert!(<f64 as Float>::nan().is_nan());
+}
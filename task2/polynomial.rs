@@ -0,0 +1,439 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub, Mul};
+use crate::{Complex, Scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PolynomialError {
+    DivisionByZero,
+    ZeroLeadingCoefficient
+}
+
+impl Display for PolynomialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            PolynomialError::DivisionByZero => write!(f, "division by the zero polynomial"),
+            PolynomialError::ZeroLeadingCoefficient => write!(f, "leading coefficient is zero")
+        };
+    }
+}
+
+impl std::error::Error for PolynomialError {}
+
+// Coefficients from the constant term up, trailing zeros are removed so the
+// zero polynomial has no coefficients at all
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<T>
+}
+
+impl<T: Scalar> Polynomial<T> {
+    pub fn new(coeffs: Vec<T>) -> Polynomial<T> {
+        let mut polynomial = Polynomial { coeffs };
+        polynomial.normalize();
+        return polynomial;
+    }
+
+    pub fn zero() -> Polynomial<T> {
+        return Polynomial { coeffs: vec![] };
+    }
+
+    // (x - r1)(x - r2)...
+    pub fn from_roots(roots: &[T]) -> Polynomial<T> {
+        let mut polynomial = Polynomial::new(vec![T::one()]);
+        for root in roots {
+            polynomial = &polynomial * &Polynomial::new(vec![-*root, T::one()]);
+        }
+        return polynomial;
+    }
+
+    fn normalize(&mut self) {
+        while self.coeffs.last().map_or(false, |coeff| *coeff == T::zero()) {
+            self.coeffs.pop();
+        }
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        return &self.coeffs;
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        return self.coeffs.len().checked_sub(1);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.coeffs.is_empty();
+    }
+
+    // Horner's scheme
+    pub fn evaluate(&self, x: T) -> T {
+        return self.coeffs.iter().rev().fold(T::zero(), |value, coeff| value * x + *coeff);
+    }
+
+    pub fn derivative(&self) -> Polynomial<T> {
+        let mut coeffs = Vec::with_capacity(self.coeffs.len().saturating_sub(1));
+        let mut factor = T::zero();
+        for coeff in self.coeffs.iter().skip(1) {
+            factor = factor + T::one();
+            coeffs.push(*coeff * factor);
+        }
+        return Polynomial::new(coeffs);
+    }
+
+    // self(inner(x)), again by Horner's scheme
+    pub fn compose(&self, inner: &Polynomial<T>) -> Polynomial<T> {
+        let mut result = Polynomial::zero();
+        for coeff in self.coeffs.iter().rev() {
+            result = &(&result * inner) + &Polynomial::new(vec![*coeff]);
+        }
+        return result;
+    }
+
+    // Long division, self = quotient * divisor + remainder with deg remainder < deg divisor
+    pub fn div_rem(&self, divisor: &Polynomial<T>) -> Result<(Polynomial<T>, Polynomial<T>), PolynomialError> {
+        let divisor_degree = match divisor.degree() {
+            Some(degree) => degree,
+            None => return Err(PolynomialError::DivisionByZero)
+        };
+        if self.coeffs.len() <= divisor_degree {
+            return Ok((Polynomial::zero(), self.clone()));
+        }
+
+        let lead = divisor.coeffs[divisor_degree];
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![T::zero(); self.coeffs.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let coeff = remainder[i + divisor_degree] / lead;
+            quotient[i] = coeff;
+            for (j, divisor_coeff) in divisor.coeffs.iter().enumerate() {
+                remainder[i + j] = remainder[i + j] - coeff * *divisor_coeff;
+            }
+        }
+        // the eliminated terms are zero by construction, not only up to rounding
+        remainder.truncate(divisor_degree);
+        return Ok((Polynomial::new(quotient), Polynomial::new(remainder)));
+    }
+
+    fn zip_with(&self, other: &Polynomial<T>, op: impl Fn(T, T) -> T) -> Polynomial<T> {
+        let len = self.coeffs.len().max(other.coeffs.len());
+        let coeff = |coeffs: &[T], i: usize| coeffs.get(i).copied().unwrap_or(T::zero());
+        return Polynomial::new((0..len).map(|i| op(coeff(&self.coeffs, i), coeff(&other.coeffs, i))).collect());
+    }
+}
+
+impl<'a, T: Scalar> Add for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, other: &'a Polynomial<T>) -> Polynomial<T> {
+        return self.zip_with(other, |a, b| a + b);
+    }
+}
+
+impl<'a, T: Scalar> Sub for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: &'a Polynomial<T>) -> Polynomial<T> {
+        return self.zip_with(other, |a, b| a - b);
+    }
+}
+
+impl<'a, T: Scalar> Mul for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: &'a Polynomial<T>) -> Polynomial<T> {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = vec![T::zero(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + *a * *b;
+            }
+        }
+        return Polynomial::new(coeffs);
+    }
+}
+
+impl<T: Scalar> Add for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, other: Polynomial<T>) -> Polynomial<T> {
+        return &self + &other;
+    }
+}
+
+impl<T: Scalar> Sub for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: Polynomial<T>) -> Polynomial<T> {
+        return &self - &other;
+    }
+}
+
+impl<T: Scalar> Mul for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: Polynomial<T>) -> Polynomial<T> {
+        return &self * &other;
+    }
+}
+
+// ----------- ROOTS ------------
+
+const MAX_ITERATIONS: usize = 1000;
+
+impl Polynomial<Complex<f64>> {
+    // Every root lies within 1 + max |a_i / a_n| of the origin (Cauchy)
+    fn root_bound(&self) -> f64 {
+        let lead = self.coeffs[self.coeffs.len() - 1];
+        return 1.0 + self.coeffs[..self.coeffs.len() - 1].iter()
+            .map(|coeff| (*coeff / lead).norm())
+            .fold(0.0, f64::max);
+    }
+
+    fn initial_guesses(&self) -> Vec<Complex<f64>> {
+        // spread over a circle, the angle offset avoids symmetric stalls
+        let degree = self.coeffs.len() - 1;
+        let radius = self.root_bound();
+        return (0..degree)
+            .map(|k| Complex::<f64>::from_polar(radius, 2.0 * std::f64::consts::PI * k as f64 / degree as f64 + 0.4))
+            .collect();
+    }
+
+    // Weierstrass / Durand–Kerner: z_k -= p(z_k) / (a_n Π_{j≠k} (z_k - z_j))
+    pub fn roots_durand_kerner(&self, tolerance: f64) -> Vec<Complex<f64>> {
+        if self.coeffs.len() <= 1 {
+            return vec![];
+        }
+        let lead = self.coeffs[self.coeffs.len() - 1];
+        let mut roots = self.initial_guesses();
+        for _ in 0..MAX_ITERATIONS {
+            let mut converged = true;
+            for k in 0..roots.len() {
+                let mut denominator = lead;
+                for j in 0..roots.len() {
+                    if j != k {
+                        denominator = denominator * (roots[k] - roots[j]);
+                    }
+                }
+                let correction = self.evaluate(roots[k]) / denominator;
+                roots[k] = roots[k] - correction;
+                converged &= correction.norm() <= tolerance * (1.0 + roots[k].norm());
+            }
+            if converged {
+                break;
+            }
+        }
+        return roots;
+    }
+
+    // Aberth–Ehrlich: a Newton step corrected by the repulsion of the other roots,
+    // converges cubically for simple roots
+    pub fn roots_aberth(&self, tolerance: f64) -> Vec<Complex<f64>> {
+        if self.coeffs.len() <= 1 {
+            return vec![];
+        }
+        let derivative = self.derivative();
+        let mut roots = self.initial_guesses();
+        for _ in 0..MAX_ITERATIONS {
+            let mut converged = true;
+            for k in 0..roots.len() {
+                let value = self.evaluate(roots[k]);
+                if value.norm() == 0.0 {
+                    continue;
+                }
+                let newton = value / derivative.evaluate(roots[k]);
+                let mut repulsion = Complex { re: 0.0, im: 0.0 };
+                for j in 0..roots.len() {
+                    if j != k {
+                        repulsion = repulsion + 1.0 / (roots[k] - roots[j]);
+                    }
+                }
+                let correction = newton / (1.0 - newton * repulsion);
+                roots[k] = roots[k] - correction;
+                converged &= correction.norm() <= tolerance * (1.0 + roots[k].norm());
+            }
+            if converged {
+                break;
+            }
+        }
+        return roots;
+    }
+
+    pub fn roots(&self) -> Vec<Complex<f64>> {
+        return self.roots_aberth(1e-14);
+    }
+}
+
+// Roots of ax² + bx + c. The root where b and the square root add up is
+// computed first, the other one from the product c/a, so neither suffers
+// from cancellation.
+pub fn solve_quadratic(a: Complex<f64>, b: Complex<f64>, c: Complex<f64>)
+    -> Result<[Complex<f64>; 2], PolynomialError>
+{
+    if a.norm() == 0.0 {
+        return Err(PolynomialError::ZeroLeadingCoefficient);
+    }
+    let mut root = (b * b - a * c * 4.0).sqrt();
+    if b.re * root.re + b.im * root.im < 0.0 {
+        root = -root;
+    }
+    let q = (b + root) * -0.5;
+    if q.norm() == 0.0 {
+        return Ok([q, q]);
+    }
+    return Ok([q / a, c / q]);
+}
+
+// Roots of ax³ + bx² + cx + d by Cardano's formula on the depressed cubic
+// t³ + pt + q with x = t - b/3a, then refined by a Newton step each
+pub fn solve_cubic(a: Complex<f64>, b: Complex<f64>, c: Complex<f64>, d: Complex<f64>)
+    -> Result<[Complex<f64>; 3], PolynomialError>
+{
+    if a.norm() == 0.0 {
+        return Err(PolynomialError::ZeroLeadingCoefficient);
+    }
+    let (b, c, d) = (b / a, c / a, d / a);
+    let p = c - b * b / 3.0;
+    let q = b * b * b * (2.0 / 27.0) - b * c / 3.0 + d;
+
+    // the sign of the square root giving the larger u³ keeps u away from zero
+    let root = (q * q / 4.0 + p * p * p / 27.0).sqrt();
+    let plus = q * -0.5 + root;
+    let minus = q * -0.5 - root;
+    let cube = if plus.norm() >= minus.norm() { plus } else { minus };
+    let u = cube.powf(1.0 / 3.0);
+
+    let omega = Complex { re: -0.5, im: 3.0f64.sqrt() / 2.0 };
+    let shift = b / 3.0;
+    let mut roots = [u, u * omega, u * omega * omega];
+    for root in roots.iter_mut() {
+        let t = if u.norm() == 0.0 { *root } else { *root - p / (*root * 3.0) };
+        *root = t - shift;
+    }
+
+    let polynomial = Polynomial::new(vec![d, c, b, Complex { re: 1.0, im: 0.0 }]);
+    let derivative = polynomial.derivative();
+    for root in roots.iter_mut() {
+        let slope = derivative.evaluate(*root);
+        if slope.norm() > 0.0 {
+            *root = *root - polynomial.evaluate(*root) / slope;
+        }
+    }
+    return Ok(roots);
+}
+
+#[cfg(test)]
+fn assert_same_roots(found: &[Complex<f64>], expected: &[Complex<f64>], tolerance: f64) {
+    assert_eq!(found.len(), expected.len());
+    // match greedily, every expected root takes the closest unused one
+    let mut unused = found.to_vec();
+    for root in expected {
+        let (index, distance) = unused.iter().enumerate()
+            .map(|(i, candidate)| (i, (*candidate - *root).norm()))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert!(distance < tolerance, "{} not found in {:?}", root, found);
+        unused.remove(index);
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let p = Polynomial::new(vec![1.0, -3.0, 0.0, 2.0]); // 2x³ - 3x + 1
+    let q = Polynomial::new(vec![-1.0, 1.0]); // x - 1
+    assert_eq!(p.degree(), Some(3));
+    assert_eq!(Polynomial::new(vec![0.0, 0.0]), Polynomial::zero());
+    assert_eq!(Polynomial::<f64>::zero().degree(), None);
+    assert_eq!(p.evaluate(2.0), 11.0);
+    assert_eq!(p.evaluate(1.0), 0.0);
+
+    assert_eq!(&p + &q, Polynomial::new(vec![0.0, -2.0, 0.0, 2.0]));
+    assert_eq!(&p - &p, Polynomial::zero());
+    assert_eq!(q.clone() * q.clone(), Polynomial::new(vec![1.0, -2.0, 1.0]));
+    assert_eq!(p.clone() * Polynomial::zero(), Polynomial::zero());
+    assert_eq!(p.clone() + q.clone() - q.clone(), p);
+    assert_eq!(p.derivative(), Polynomial::new(vec![-3.0, 0.0, 6.0]));
+    assert_eq!(Polynomial::new(vec![5.0]).derivative(), Polynomial::zero());
+    assert_eq!(Polynomial::from_roots(&[1.0, 2.0]), Polynomial::new(vec![2.0, -3.0, 1.0]));
+
+    let (quotient, remainder) = p.div_rem(&q).unwrap();
+    assert_eq!(quotient, Polynomial::new(vec![-1.0, 2.0, 2.0]));
+    assert_eq!(remainder, Polynomial::zero());
+    let (quotient, remainder) = p.div_rem(&Polynomial::new(vec![1.0, 0.0, 1.0])).unwrap();
+    assert_eq!(quotient, Polynomial::new(vec![0.0, 2.0]));
+    assert_eq!(remainder, Polynomial::new(vec![1.0, -5.0]));
+    assert_eq!(q.div_rem(&p).unwrap(), (Polynomial::zero(), q.clone()));
+    assert_eq!(p.div_rem(&Polynomial::zero()), Err(PolynomialError::DivisionByZero));
+
+    // p(x - 1)
+    let shifted = p.compose(&q);
+    assert_eq!(shifted, Polynomial::new(vec![2.0, 3.0, -6.0, 2.0]));
+    for x in [-2.0, 0.5, 3.0] {
+        assert_eq!(shifted.evaluate(x), p.evaluate(x - 1.0));
+    }
+}
+
+#[test]
+fn test_complex_roots() {
+    use crate::prng::{Rng, Xoshiro256};
+    let c = |re: f64, im: f64| Complex { re, im };
+
+    // x² + 1
+    let p = Polynomial::new(vec![c(1.0, 0.0), c(0.0, 0.0), c(1.0, 0.0)]);
+    assert_same_roots(&p.roots(), &[c(0.0, 1.0), c(0.0, -1.0)], 1e-12);
+    assert_same_roots(&p.roots_durand_kerner(1e-14), &[c(0.0, 1.0), c(0.0, -1.0)], 1e-12);
+    assert_eq!(Polynomial::new(vec![c(3.0, 0.0)]).roots(), vec![]);
+
+    // x⁵ - 1, the fifth roots of unity
+    let p = Polynomial::new(vec![c(-1.0, 0.0), c(0.0, 0.0), c(0.0, 0.0), c(0.0, 0.0), c(0.0, 0.0), c(1.0, 0.0)]);
+    let unity: Vec<Complex<f64>> = (0..5).map(|k| Complex::<f64>::from_polar(1.0, 2.0 * std::f64::consts::PI * k as f64 / 5.0)).collect();
+    assert_same_roots(&p.roots(), &unity, 1e-12);
+
+    // a double root only converges linearly and to about half the precision
+    let p = Polynomial::from_roots(&[c(1.0, 1.0), c(1.0, 1.0), c(-2.0, 0.5)]);
+    assert_same_roots(&p.roots(), &[c(1.0, 1.0), c(1.0, 1.0), c(-2.0, 0.5)], 1e-6);
+
+    let mut rng = Xoshiro256::new(42);
+    for degree in [1, 2, 3, 5, 8, 12, 20] {
+        let expected: Vec<Complex<f64>> = (0..degree).map(|_| c(rng.range(-3.0..3.0), rng.range(-3.0..3.0))).collect();
+        let p = &Polynomial::from_roots(&expected) * &Polynomial::new(vec![c(rng.range(0.5..2.0), rng.range(-1.0..1.0))]);
+        assert_same_roots(&p.roots_aberth(1e-14), &expected, 1e-7);
+        assert_same_roots(&p.roots_durand_kerner(1e-14), &expected, 1e-7);
+    }
+}
+
+#[test]
+fn test_closed_form() {
+    use crate::prng::{Rng, Xoshiro256};
+    let c = |re: f64, im: f64| Complex { re, im };
+    let zero = c(0.0, 0.0);
+
+    assert_same_roots(&solve_quadratic(c(1.0, 0.0), c(-3.0, 0.0), c(2.0, 0.0)).unwrap(), &[c(1.0, 0.0), c(2.0, 0.0)], 1e-15);
+    assert_same_roots(&solve_quadratic(c(1.0, 0.0), zero, c(4.0, 0.0)).unwrap(), &[c(0.0, 2.0), c(0.0, -2.0)], 1e-15);
+    assert_eq!(solve_quadratic(c(2.0, 0.0), zero, zero), Ok([zero, zero]));
+    assert_eq!(solve_quadratic(zero, c(1.0, 0.0), c(1.0, 0.0)), Err(PolynomialError::ZeroLeadingCoefficient));
+
+    // the naive formula loses the small root to cancellation
+    let roots = solve_quadratic(c(1.0, 0.0), c(-1e8, 0.0), c(1.0, 0.0)).unwrap();
+    assert_same_roots(&roots, &[c(1e8, 0.0), c(1e-8, 0.0)], 1e-16 * 1e8);
+    assert!((roots[1].re - 1e-8).abs() < 1e-22);
+
+    // (x - 1)(x - 2)(x - 3)
+    let roots = solve_cubic(c(1.0, 0.0), c(-6.0, 0.0), c(11.0, 0.0), c(-6.0, 0.0)).unwrap();
+    assert_same_roots(&roots, &[c(1.0, 0.0), c(2.0, 0.0), c(3.0, 0.0)], 1e-12);
+    assert_same_roots(&solve_cubic(c(1.0, 0.0), zero, zero, zero).unwrap(), &[zero, zero, zero], 1e-15);
+    assert_same_roots(&solve_cubic(c(1.0, 0.0), zero, zero, c(-8.0, 0.0)).unwrap(),
+                      &[c(2.0, 0.0), c(-1.0, 3.0f64.sqrt()), c(-1.0, -3.0f64.sqrt())], 1e-12);
+    assert_eq!(solve_cubic(zero, zero, zero, zero), Err(PolynomialError::ZeroLeadingCoefficient));
+
+    let mut rng = Xoshiro256::new(42);
+    for _ in 0..200 {
+        let expected: Vec<Complex<f64>> = (0..3).map(|_| c(rng.range(-5.0..5.0), rng.range(-5.0..5.0))).collect();
+        let a = c(rng.range(0.5..2.0), rng.range(-1.0..1.0));
+        let p = &Polynomial::from_roots(&expected) * &Polynomial::new(vec![a]);
+        let [d, c1, b, a] = [p.coeffs()[0], p.coeffs()[1], p.coeffs()[2], p.coeffs()[3]];
+        assert_same_roots(&solve_cubic(a, b, c1, d).unwrap(), &expected, 1e-8);
+        assert_same_roots(&solve_quadratic(a, b, c1).unwrap(), &Polynomial::new(vec![c1, b, a]).roots(), 1e-8);
+    }
+}
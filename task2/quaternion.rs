@@ -0,0 +1,724 @@
+use std::fmt::{Display, Formatter, LowerExp, UpperExp};
+use std::ops::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign, Neg, Range};
+use crate::prng::{Rng, SampleUniform};
+use crate::complex::{Complex, write_parts};
+use crate::num::{Num, Float};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuaternionError {
+    ZeroNorm,
+    ZeroAxis
+}
+
+impl Display for QuaternionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            QuaternionError::ZeroNorm => write!(f, "quaternion has zero norm"),
+            QuaternionError::ZeroAxis => write!(f, "rotation axis has zero length")
+        };
+    }
+}
+
+impl std::error::Error for QuaternionError {}
+
+// w + xi + yj + zk with i² = j² = k² = ijk = -1. The product is not
+// commutative, a / b is a * b⁻¹.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion<T> {
+    pub w: T,
+    pub x: T,
+    pub y: T,
+    pub z: T
+}
+
+impl<T> Quaternion<T> {
+    pub fn new(w: T, x: T, y: T, z: T) -> Quaternion<T> {
+        return Quaternion { w, x, y, z };
+    }
+}
+
+impl<T: Neg<Output = T> + Copy> Quaternion<T> {
+    pub fn conj(&self) -> Quaternion<T> {
+        return Quaternion {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z
+        };
+    }
+}
+
+impl<T: SampleUniform + Copy> Quaternion<T> {
+    // all four parts are drawn independently from the same range
+    pub fn random<R: Rng>(rng: &mut R, range: Range<T>) -> Quaternion<T> {
+        return Quaternion {
+            w: rng.range(range.start..range.end),
+            x: rng.range(range.start..range.end),
+            y: rng.range(range.start..range.end),
+            z: rng.range(range.start..range.end)
+        };
+    }
+}
+
+// ----------- CAYLEY–DICKSON ------------
+
+// The pair (a, b) of complex numbers stands for a + bj. Because jz = z̄j the
+// product of two pairs is (a, b)(c, d) = (ac - b d̄, ad + b c̄).
+impl<T> From<(Complex<T>, Complex<T>)> for Quaternion<T> {
+    fn from((a, b): (Complex<T>, Complex<T>)) -> Quaternion<T> {
+        return Quaternion {
+            w: a.re,
+            x: a.im,
+            y: b.re,
+            z: b.im
+        };
+    }
+}
+
+impl<T> From<Quaternion<T>> for (Complex<T>, Complex<T>) {
+    fn from(q: Quaternion<T>) -> (Complex<T>, Complex<T>) {
+        return (Complex { re: q.w, im: q.x }, Complex { re: q.y, im: q.z });
+    }
+}
+
+// ----------- OPERATORS ------------
+
+impl<T: Add<Output = T>> Add for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn add(self, other: Self) -> Self::Output {
+        return Quaternion {
+            w: self.w + other.w,
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z
+        };
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        return Quaternion {
+            w: self.w - other.w,
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z
+        };
+    }
+}
+
+// Hamilton product
+impl<T: Num> Mul for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        return Quaternion {
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
+        };
+    }
+}
+
+impl<T: AddAssign<T>> AddAssign for Quaternion<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.w += other.w;
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign<T>> SubAssign for Quaternion<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.w -= other.w;
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+// multiplies from the right, q *= p is q = q * p
+impl<T: Num> MulAssign for Quaternion<T> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn neg(self) -> Self::Output {
+        return Quaternion {
+            w: -self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z
+        };
+    }
+}
+
+impl<'a, 'b, T> Add<&'b Quaternion<T>> for &'a Quaternion<T> where &'a T: Add<&'b T, Output = T> {
+    type Output = Quaternion<T>;
+
+    fn add(self, other: &'b Quaternion<T>) -> Quaternion<T> {
+        return Quaternion {
+            w: &self.w + &other.w,
+            x: &self.x + &other.x,
+            y: &self.y + &other.y,
+            z: &self.z + &other.z
+        };
+    }
+}
+
+impl<'a, 'b, T> Sub<&'b Quaternion<T>> for &'a Quaternion<T> where &'a T: Sub<&'b T, Output = T> {
+    type Output = Quaternion<T>;
+
+    fn sub(self, other: &'b Quaternion<T>) -> Quaternion<T> {
+        return Quaternion {
+            w: &self.w - &other.w,
+            x: &self.x - &other.x,
+            y: &self.y - &other.y,
+            z: &self.z - &other.z
+        };
+    }
+}
+
+impl<'a, 'b, T> Mul<&'b Quaternion<T>> for &'a Quaternion<T>
+    where T: Add<Output = T> + Sub<Output = T>, &'a T: Mul<&'b T, Output = T>
+{
+    type Output = Quaternion<T>;
+
+    fn mul(self, other: &'b Quaternion<T>) -> Quaternion<T> {
+        return Quaternion {
+            w: &self.w * &other.w - &self.x * &other.x - &self.y * &other.y - &self.z * &other.z,
+            x: &self.w * &other.x + &self.x * &other.w + &self.y * &other.z - &self.z * &other.y,
+            y: &self.w * &other.y - &self.x * &other.z + &self.y * &other.w + &self.z * &other.x,
+            z: &self.w * &other.z + &self.x * &other.y - &self.y * &other.x + &self.z * &other.w
+        };
+    }
+}
+
+impl<'a, T> Neg for &'a Quaternion<T> where &'a T: Neg<Output = T> {
+    type Output = Quaternion<T>;
+
+    fn neg(self) -> Quaternion<T> {
+        return Quaternion {
+            w: -&self.w,
+            x: -&self.x,
+            y: -&self.y,
+            z: -&self.z
+        };
+    }
+}
+
+// ----------- SCALAR OPERATORS ------------
+
+impl<T: Add<Output = T>> Add<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn add(self, other: T) -> Self::Output {
+        return Quaternion { w: self.w + other, ..self };
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn sub(self, other: T) -> Self::Output {
+        return Quaternion { w: self.w - other, ..self };
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn mul(self, other: T) -> Self::Output {
+        return Quaternion {
+            w: self.w * other,
+            x: self.x * other,
+            y: self.y * other,
+            z: self.z * other
+        };
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn div(self, other: T) -> Self::Output {
+        return Quaternion {
+            w: self.w / other,
+            x: self.x / other,
+            y: self.y / other,
+            z: self.z / other
+        };
+    }
+}
+
+impl<T: AddAssign<T>> AddAssign<T> for Quaternion<T> {
+    fn add_assign(&mut self, other: T) {
+        self.w += other;
+    }
+}
+
+impl<T: SubAssign<T>> SubAssign<T> for Quaternion<T> {
+    fn sub_assign(&mut self, other: T) {
+        self.w -= other;
+    }
+}
+
+impl<T: MulAssign<T> + Copy> MulAssign<T> for Quaternion<T> {
+    fn mul_assign(&mut self, other: T) {
+        self.w *= other;
+        self.x *= other;
+        self.y *= other;
+        self.z *= other;
+    }
+}
+
+impl<T: DivAssign<T> + Copy> DivAssign<T> for Quaternion<T> {
+    fn div_assign(&mut self, other: T) {
+        self.w /= other;
+        self.x /= other;
+        self.y /= other;
+        self.z /= other;
+    }
+}
+
+// Real scalars commute with every quaternion, so the left hand side only
+// needs its own impls because it can not be generic
+macro_rules! impl_scalar_lhs {
+    ($($t:ty),*) => {$(
+        impl Add<Quaternion<$t>> for $t {
+            type Output = Quaternion<$t>;
+
+            fn add(self, other: Quaternion<$t>) -> Quaternion<$t> {
+                return other + self;
+            }
+        }
+
+        impl Sub<Quaternion<$t>> for $t {
+            type Output = Quaternion<$t>;
+
+            fn sub(self, other: Quaternion<$t>) -> Quaternion<$t> {
+                let zero = 0 as $t;
+                return Quaternion {
+                    w: self - other.w,
+                    x: zero - other.x,
+                    y: zero - other.y,
+                    z: zero - other.z
+                };
+            }
+        }
+
+        impl Mul<Quaternion<$t>> for $t {
+            type Output = Quaternion<$t>;
+
+            fn mul(self, other: Quaternion<$t>) -> Quaternion<$t> {
+                return other * self;
+            }
+        }
+    )*};
+}
+
+impl_scalar_lhs!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+// ----------- FUNCTIONS ------------
+
+// Rotations use unit quaternions: q = cos(θ/2) + sin(θ/2)(xi + yj + zk) turns
+// points by θ around the axis (x, y, z), right handed, as p' = q p q⁻¹. Euler
+// angles are roll about x, pitch about y and yaw about z, applied in that
+// order to the point, i.e. q = yaw * pitch * roll.
+impl<T: Float> Quaternion<T> {
+    pub fn norm_sqr(&self) -> T {
+        return self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z;
+    }
+
+    pub fn norm(&self) -> T {
+        return self.w.hypot(self.x).hypot(self.y.hypot(self.z));
+    }
+
+    pub fn normalize(&self) -> Result<Quaternion<T>, QuaternionError> {
+        let norm = self.norm();
+        if norm.is_zero() {
+            return Err(QuaternionError::ZeroNorm);
+        }
+        return Ok(*self / norm);
+    }
+
+    // dividing by the norm twice instead of its square avoids overflow
+    pub fn inverse(&self) -> Result<Quaternion<T>, QuaternionError> {
+        let norm = self.norm();
+        if norm.is_zero() {
+            return Err(QuaternionError::ZeroNorm);
+        }
+        return Ok(self.conj() / norm / norm);
+    }
+
+    pub fn checked_div(&self, other: &Quaternion<T>) -> Result<Quaternion<T>, QuaternionError> {
+        return Ok(*self * other.inverse()?);
+    }
+
+    pub fn from_axis_angle(axis: [T; 3], angle: T) -> Result<Quaternion<T>, QuaternionError> {
+        let length = axis[0].hypot(axis[1]).hypot(axis[2]);
+        if length.is_zero() {
+            return Err(QuaternionError::ZeroAxis);
+        }
+        let (sin, cos) = (angle / T::two()).sin_cos();
+        return Ok(Quaternion {
+            w: cos,
+            x: sin * axis[0] / length,
+            y: sin * axis[1] / length,
+            z: sin * axis[2] / length
+        });
+    }
+
+    // The angle is in [0, 2π], the rotation by zero has the x axis. The
+    // quaternion does not have to be normalized.
+    pub fn to_axis_angle(&self) -> ([T; 3], T) {
+        let length = self.x.hypot(self.y).hypot(self.z);
+        let angle = T::two() * length.atan2(self.w);
+        if length.is_zero() {
+            return ([T::one(), T::zero(), T::zero()], angle);
+        }
+        return ([self.x / length, self.y / length, self.z / length], angle);
+    }
+
+    pub fn from_euler(roll: T, pitch: T, yaw: T) -> Quaternion<T> {
+        let (sr, cr) = (roll / T::two()).sin_cos();
+        let (sp, cp) = (pitch / T::two()).sin_cos();
+        let (sy, cy) = (yaw / T::two()).sin_cos();
+        return Quaternion {
+            w: cr * cp * cy + sr * sp * sy,
+            x: sr * cp * cy - cr * sp * sy,
+            y: cr * sp * cy + sr * cp * sy,
+            z: cr * cp * sy - sr * sp * cy
+        };
+    }
+
+    // (roll, pitch, yaw) with pitch in [-π/2, π/2] and the others in (-π, π].
+    // At pitch ±π/2 only roll ∓ yaw is defined, roll is taken as zero then.
+    pub fn to_euler(&self) -> (T, T, T) {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+        let (one, two, pi) = (T::one(), T::two(), T::pi());
+        let mut sin_pitch = two * (w * y - z * x) / self.norm_sqr();
+        if sin_pitch > one {
+            sin_pitch = one;
+        } else if sin_pitch < -one {
+            sin_pitch = -one;
+        }
+        if sin_pitch.abs() >= one - T::from_f64(8.0) * T::epsilon() {
+            let mut yaw = -two * sin_pitch.signum() * x.atan2(w);
+            if yaw > pi {
+                yaw = yaw - two * pi;
+            } else if yaw <= -pi {
+                yaw = yaw + two * pi;
+            }
+            return (T::zero(), (pi / two).copysign(sin_pitch), yaw);
+        }
+        let roll = (two * (w * x + y * z)).atan2(w * w - x * x - y * y + z * z);
+        let yaw = (two * (w * z + x * y)).atan2(w * w + x * x - y * y - z * z);
+        return (roll, sin_pitch.asin(), yaw);
+    }
+
+    // Spherical interpolation between unit quaternions along the shorter
+    // arc, t = 0 gives self and t = 1 gives other or its negative
+    pub fn slerp(&self, other: &Quaternion<T>, t: T) -> Quaternion<T> {
+        let mut end = *other;
+        let mut dot = self.w * end.w + self.x * end.x + self.y * end.y + self.z * end.z;
+        if dot.is_negative() {
+            end = -end;
+            dot = -dot;
+        }
+        // sin θ is too small to divide by, the chord is as good as the arc
+        if dot > T::from_f64(0.9995) {
+            let lerp = *self + (end - *self) * t;
+            return lerp / lerp.norm();
+        }
+        let theta = dot.acos();
+        let sin = theta.sin();
+        return *self * (((T::one() - t) * theta).sin() / sin) + end * ((t * theta).sin() / sin);
+    }
+
+    // q p q⁻¹ with p = xi + yj + zk, written out for the unit quaternion
+    // p + 2w(v × p) + 2v × (v × p). Panics for the zero quaternion.
+    pub fn rotate(&self, point: [T; 3]) -> [T; 3] {
+        let q = match self.normalize() {
+            Ok(q) => q,
+            Err(error) => panic!("{}", error)
+        };
+        let cross = |a: [T; 3], b: [T; 3]| [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0]
+        ];
+        let v = [q.x, q.y, q.z];
+        let t = cross(v, point);
+        let u = cross(v, t);
+        let two = T::two();
+        return [
+            point[0] + two * (q.w * t[0] + u[0]),
+            point[1] + two * (q.w * t[1] + u[1]),
+            point[2] + two * (q.w * t[2] + u[2])
+        ];
+    }
+}
+
+// The operators panic on a zero divisor, use checked_div to handle it
+impl<T: Float> Div for Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn div(self, other: Self) -> Self::Output {
+        return match self.checked_div(&other) {
+            Ok(result) => result,
+            Err(_) => panic!("quaternion division by zero")
+        };
+    }
+}
+
+impl<T: Float> DivAssign for Quaternion<T> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<'a, 'b, T: Float> Div<&'b Quaternion<T>> for &'a Quaternion<T> {
+    type Output = Quaternion<T>;
+
+    fn div(self, other: &'b Quaternion<T>) -> Quaternion<T> {
+        return *self / *other;
+    }
+}
+
+macro_rules! impl_scalar_lhs_div {
+    ($($t:ty),*) => {$(
+        impl Div<Quaternion<$t>> for $t {
+            type Output = Quaternion<$t>;
+
+            fn div(self, other: Quaternion<$t>) -> Quaternion<$t> {
+                return Quaternion { w: self, x: 0.0, y: 0.0, z: 0.0 } / other;
+            }
+        }
+    )*};
+}
+
+impl_scalar_lhs_div!(f32, f64);
+
+// ----------- FORMATTING ------------
+
+macro_rules! impl_quaternion_format {
+    ($($format:ident => $plain:literal, $precise:literal);*) => {$(
+        impl<T: $format> $format for Quaternion<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                let part = |value: &T| match f.precision() {
+                    Some(precision) => format!($precise, value, precision = precision),
+                    None => format!($plain, value)
+                };
+                let imaginary = [(part(&self.x), 'i'), (part(&self.y), 'j'), (part(&self.z), 'k')];
+                return write_parts(f, part(&self.w), &imaginary);
+            }
+        }
+    )*};
+}
+
+impl_quaternion_format!(
+    Display => "{}", "{:.precision$}";
+    LowerExp => "{:e}", "{:.precision$e}";
+    UpperExp => "{:E}", "{:.precision$E}"
+);
+
+#[cfg(test)]
+fn close(a: Quaternion<f64>, b: Quaternion<f64>) -> bool {
+    return (a - b).norm() <= 1e-12 * (1.0 + b.norm());
+}
+
+#[cfg(test)]
+fn close_point(a: [f64; 3], b: [f64; 3]) -> bool {
+    return a.iter().zip(&b).all(|(a, b)| (a - b).abs() <= 1e-12 * (1.0 + b.abs()));
+}
+
+#[test]
+fn test_operations() {
+    let q = |w: i64, x: i64, y: i64, z: i64| Quaternion::new(w, x, y, z);
+    let (one, i, j, k) = (q(1, 0, 0, 0), q(0, 1, 0, 0), q(0, 0, 1, 0), q(0, 0, 0, 1));
+    assert_eq!(i * i, -one);
+    assert_eq!(j * j, -one);
+    assert_eq!(k * k, -one);
+    assert_eq!(i * j * k, -one);
+    assert_eq!(i * j, k);
+    assert_eq!(j * i, -k);
+    assert_eq!(k * i, j);
+
+    let (a, b) = (q(1, 2, 3, 4), q(-2, 0, 5, -1));
+    assert_eq!(a + b, q(-1, 2, 8, 3));
+    assert_eq!(a - b, q(3, 2, -2, 5));
+    assert_eq!(a * b, q(-13, -27, 1, 1));
+    assert_eq!(b * a, q(-13, 19, -3, -19));
+    assert_eq!(a * a.conj(), q(30, 0, 0, 0));
+    assert_eq!((a * b).conj(), b.conj() * a.conj());
+
+    assert_eq!(&a + &b, a + b);
+    assert_eq!(&a - &b, a - b);
+    assert_eq!(&a * &b, a * b);
+    assert_eq!(-&a, -a);
+
+    assert_eq!(a + 2, q(3, 2, 3, 4));
+    assert_eq!(a - 2, q(-1, 2, 3, 4));
+    assert_eq!(a * 2, q(2, 4, 6, 8));
+    assert_eq!(q(2, 4, 6, 8) / 2, a);
+    assert_eq!(2 + a, a + 2);
+    assert_eq!(2 - a, q(1, -2, -3, -4));
+    assert_eq!(2 * a, a * 2);
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    assert_eq!(c, b);
+    c *= a;
+    assert_eq!(c, b * a);
+    c += 1;
+    c -= 2;
+    c *= 3;
+    assert_eq!(c, (b * a - 1) * 3);
+    c /= 3;
+    assert_eq!(c, b * a - 1);
+
+    // the product of the pairs matches the Hamilton product
+    let mut rng = crate::prng::Pcg32::new(44, 0);
+    for _ in 0..1000 {
+        let a: Quaternion<i64> = Quaternion::random(&mut rng, -1000..1000);
+        let b: Quaternion<i64> = Quaternion::random(&mut rng, -1000..1000);
+        let ((p, q), (r, s)): ((Complex<i64>, Complex<i64>), (Complex<i64>, Complex<i64>)) = (a.into(), b.into());
+        assert_eq!(Quaternion::from((p, q)), a);
+        assert_eq!(Quaternion::from((p * r - q * s.conj(), p * s + q * r.conj())), a * b);
+    }
+
+    let val: Quaternion<f64> = Quaternion::new(1.0, -2.5, 0.0, 3.0);
+    assert_eq!(format!("{}", val), "1-2.5i+0j+3k");
+    assert_eq!(format!("{:+.1}", val), "+1.0-2.5i+0.0j+3.0k");
+    assert_eq!(format!("{:e}", val), "1e0-2.5e0i+0e0j+3e0k");
+    assert_eq!(format!("{:>12}", q(1, 2, -3, 4)), "  1+2i-3j+4k");
+    assert_eq!(format!("{:*<14}", q(1, 2, -3, 4)), "1+2i-3j+4k****");
+}
+
+#[test]
+fn test_division() {
+    let a: Quaternion<f64> = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+    let b: Quaternion<f64> = Quaternion::new(-2.0, 0.5, 5.0, -1.0);
+    let zero: Quaternion<f64> = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+    let one: Quaternion<f64> = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+
+    assert_eq!(a.norm_sqr(), 30.0);
+    assert!((a.norm() - 30f64.sqrt()).abs() < 1e-15);
+    assert!(close(a * a.inverse().unwrap(), one));
+    assert!(close(a.inverse().unwrap() * a, one));
+    assert!(close(a / b * b, a));
+    assert!(close(&a / &b, a / b));
+    assert!(close(2.0 / a, a.inverse().unwrap() * 2.0));
+    assert!((a.normalize().unwrap().norm() - 1.0).abs() < 1e-15);
+
+    let mut c = a;
+    c /= b;
+    assert!(close(c, a * b.inverse().unwrap()));
+
+    assert_eq!(zero.inverse(), Err(QuaternionError::ZeroNorm));
+    assert_eq!(zero.normalize(), Err(QuaternionError::ZeroNorm));
+    assert_eq!(a.checked_div(&zero), Err(QuaternionError::ZeroNorm));
+
+    // the inverse of a huge quaternion does not overflow to zero
+    let huge: Quaternion<f64> = Quaternion::new(1e300, 1e300, 0.0, 0.0);
+    assert!(close(huge * huge.inverse().unwrap(), one));
+
+    let small: Quaternion<f32> = Quaternion::new(0.0, 3.0, 0.0, 4.0);
+    assert_eq!(small.norm(), 5.0);
+    assert_eq!(small.norm_sqr(), 25.0);
+    assert!((small / small - Quaternion::new(1.0, 0.0, 0.0, 0.0)).norm() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "quaternion division by zero")]
+fn test_division_by_zero() {
+    let _ = Quaternion::new(1.0, 2.0, 3.0, 4.0) / Quaternion::new(0.0, 0.0, 0.0, 0.0);
+}
+
+#[test]
+fn test_rotations() {
+    use std::f64::consts::{PI, FRAC_PI_2};
+    use crate::prng::Xoshiro256;
+
+    let quarter = Quaternion::<f64>::from_axis_angle([0.0, 0.0, 2.0], FRAC_PI_2).unwrap();
+    assert!(close_point(quarter.rotate([1.0, 0.0, 0.0]), [0.0, 1.0, 0.0]));
+    assert!(close_point(quarter.rotate([0.0, 1.0, 3.0]), [-1.0, 0.0, 3.0]));
+    assert!(close_point((quarter * 5.0).rotate([1.0, 0.0, 0.0]), [0.0, 1.0, 0.0]));
+    assert!(close_point((quarter * quarter).rotate([1.0, 2.0, 3.0]), [-1.0, -2.0, 3.0]));
+    assert_eq!(Quaternion::<f64>::from_axis_angle([0.0; 3], 1.0), Err(QuaternionError::ZeroAxis));
+
+    let (axis, angle) = quarter.to_axis_angle();
+    assert!(close_point(axis, [0.0, 0.0, 1.0]) && (angle - FRAC_PI_2).abs() < 1e-15);
+    assert_eq!(Quaternion::<f64>::new(1.0, 0.0, 0.0, 0.0).to_axis_angle(), ([1.0, 0.0, 0.0], 0.0));
+
+    // yaw only is a rotation about z, roll only one about x
+    assert!(close(Quaternion::<f64>::from_euler(0.0, 0.0, FRAC_PI_2), quarter));
+    let roll = Quaternion::<f64>::from_euler(FRAC_PI_2, 0.0, 0.0);
+    assert!(close_point(roll.rotate([0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]));
+
+    let mut rng = Xoshiro256::new(44);
+    for _ in 0..1000 {
+        let axis = [rng.range(-1.0..1.0), rng.range(-1.0..1.0), rng.range(-1.0..1.0)];
+        let angle = rng.range(0.0..2.0 * PI);
+        let q = Quaternion::<f64>::from_axis_angle(axis, angle).unwrap();
+        assert!((q.norm() - 1.0).abs() < 1e-14);
+        let (back_axis, back_angle) = q.to_axis_angle();
+        assert!(close(Quaternion::<f64>::from_axis_angle(back_axis, back_angle).unwrap(), q));
+
+        // rotations keep lengths and compose like the product
+        let p = Quaternion::<f64>::random(&mut rng, -1.0..1.0);
+        let point = [rng.range(-10.0..10.0), rng.range(-10.0..10.0), rng.range(-10.0..10.0)];
+        let rotated = q.rotate(point);
+        let length = |v: [f64; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        assert!((length(rotated) - length(point)).abs() < 1e-12);
+        assert!(close_point((p * q).rotate(point), p.rotate(rotated)));
+
+        let (roll, pitch, yaw) = (rng.range(-PI..PI), rng.range(-1.5..1.5), rng.range(-PI..PI));
+        let q = Quaternion::<f64>::from_euler(roll, pitch, yaw);
+        let (r, p, y) = q.to_euler();
+        assert!((r - roll).abs() < 1e-9 && (p - pitch).abs() < 1e-9 && (y - yaw).abs() < 1e-9);
+        let x_axis = Quaternion::<f64>::from_axis_angle([1.0, 0.0, 0.0], roll).unwrap();
+        let y_axis = Quaternion::<f64>::from_axis_angle([0.0, 1.0, 0.0], pitch).unwrap();
+        let z_axis = Quaternion::<f64>::from_axis_angle([0.0, 0.0, 1.0], yaw).unwrap();
+        assert!(close(z_axis * y_axis * x_axis, q));
+    }
+
+    // in gimbal lock the angles differ but the rotation is the same
+    for (roll, pitch, yaw) in [(0.3, FRAC_PI_2, -1.2), (2.5, -FRAC_PI_2, 3.0), (-3.0, FRAC_PI_2, 3.0)] {
+        let q = Quaternion::<f64>::from_euler(roll, pitch, yaw);
+        let (r, p, y) = q.to_euler();
+        assert_eq!((r, p), (0.0, pitch));
+        assert!(y > -PI && y <= PI);
+        assert!(close_point(Quaternion::<f64>::from_euler(r, p, y).rotate([1.0, 2.0, 3.0]), q.rotate([1.0, 2.0, 3.0])));
+    }
+
+    // slerp keeps the unit length and turns with constant speed
+    let start = Quaternion::<f64>::from_axis_angle([1.0, 1.0, 0.0], 0.2).unwrap();
+    let end = Quaternion::<f64>::from_axis_angle([1.0, 1.0, 0.0], 2.2).unwrap();
+    assert!(close(start.slerp(&end, 0.0), start));
+    assert!(close(start.slerp(&end, 1.0), end));
+    assert!(close(start.slerp(&end, 0.25), Quaternion::<f64>::from_axis_angle([1.0, 1.0, 0.0], 0.7).unwrap()));
+    assert!(close(start.slerp(&-end, 0.5), Quaternion::<f64>::from_axis_angle([1.0, 1.0, 0.0], 1.2).unwrap()));
+    let near = Quaternion::<f64>::from_axis_angle([1.0, 1.0, 0.0], 0.2001).unwrap();
+    let middle = start.slerp(&near, 0.5);
+    assert!((middle.norm() - 1.0).abs() < 1e-15);
+    assert!(close(middle, Quaternion::<f64>::from_axis_angle([1.0, 1.0, 0.0], 0.20005).unwrap()));
+
+    let single = Quaternion::<f32>::from_euler(0.1, 0.2, 0.3);
+    let (r, p, y) = single.to_euler();
+    assert!((r - 0.1).abs() < 1e-6 && (p - 0.2).abs() < 1e-6 && (y - 0.3).abs() < 1e-6);
+    let (_, angle) = single.slerp(&single.conj(), 0.5).to_axis_angle();
+    assert!(angle.abs() < 1e-6);
+    assert!(single.inverse().is_ok() && single.rotate([1.0, 0.0, 0.0])[0] > 0.0);
+    assert!(Quaternion::<f32>::from_axis_angle([1.0, 0.0, 0.0], 1.0).is_ok());
+}
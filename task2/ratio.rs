@@ -0,0 +1,525 @@
+// Exact fractions, shared by the task2 crates so they work as Complex
+// components and Rectangle coordinates: mod num; mod ratio; mod complex;
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::{Sum, Product};
+use std::str::FromStr;
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+use crate::num::{Zero, One, Signed, Integer};
+use crate::complex::{Complex, ComplexDiv, ComplexError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RatioError {
+    ZeroDenominator,
+    InvalidNumber,
+    OutOfRange
+}
+
+impl Display for RatioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            RatioError::ZeroDenominator => write!(f, "denominator is zero"),
+            RatioError::InvalidNumber => write!(f, "invalid number"),
+            RatioError::OutOfRange => write!(f, "value does not fit the ratio")
+        };
+    }
+}
+
+impl std::error::Error for RatioError {}
+
+// Always in lowest terms with a positive denominator, so equal values have
+// equal fields and the derived PartialEq and Hash are correct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T
+}
+
+impl<T: Integer + Signed> Ratio<T> {
+    // Panics on a zero denominator or a value that does not fit, checked_new
+    // returns an error instead
+    pub fn new(numer: T, denom: T) -> Ratio<T> {
+        return match Ratio::checked_new(numer, denom) {
+            Ok(ratio) => ratio,
+            Err(error) => panic!("{}", error)
+        };
+    }
+
+    pub fn checked_new(numer: T, denom: T) -> Result<Ratio<T>, RatioError> {
+        if denom.is_zero() {
+            return Err(RatioError::ZeroDenominator);
+        }
+        // gcd(MIN, MIN) is out of range, the ratio itself is one
+        let gcd = numer.checked_gcd(denom).unwrap_or(denom);
+        let (numer, denom) = (numer / gcd, denom / gcd);
+        if denom.is_negative() {
+            // -MIN does not fit, e.g. 1 / i64::MIN
+            let negate = |value: T| value.to_i128().checked_neg().and_then(T::from_i128);
+            return match (negate(numer), negate(denom)) {
+                (Some(numer), Some(denom)) => Ok(Ratio { numer, denom }),
+                _ => Err(RatioError::OutOfRange)
+            };
+        }
+        return Ok(Ratio { numer, denom });
+    }
+
+    pub fn from_integer(value: T) -> Ratio<T> {
+        return Ratio { numer: value, denom: T::one() };
+    }
+
+    pub fn numer(&self) -> T {
+        return self.numer;
+    }
+
+    pub fn denom(&self) -> T {
+        return self.denom;
+    }
+
+    pub fn is_integer(&self) -> bool {
+        return self.denom == T::one();
+    }
+
+    pub fn recip(&self) -> Ratio<T> {
+        return Ratio::new(self.denom, self.numer);
+    }
+
+    pub fn pow(&self, exponent: i32) -> Ratio<T> {
+        let mut base = if exponent < 0 { self.recip() } else { *self };
+        let mut exponent = exponent.unsigned_abs();
+        let mut result = Ratio::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        return result;
+    }
+
+    // ----------- ROUNDING ------------
+
+    // quotient and remainder with the remainder in [0, denom)
+    fn floor_div(&self) -> (T, T) {
+        let (quotient, remainder) = (self.numer / self.denom, self.numer % self.denom);
+        if remainder.is_negative() {
+            return (quotient - T::one(), remainder + self.denom);
+        }
+        return (quotient, remainder);
+    }
+
+    pub fn floor(&self) -> Ratio<T> {
+        return Ratio::from_integer(self.floor_div().0);
+    }
+
+    pub fn ceil(&self) -> Ratio<T> {
+        let (quotient, remainder) = self.floor_div();
+        if remainder.is_zero() {
+            return Ratio::from_integer(quotient);
+        }
+        return Ratio::from_integer(quotient + T::one());
+    }
+
+    pub fn trunc(&self) -> Ratio<T> {
+        return Ratio::from_integer(self.numer / self.denom);
+    }
+
+    // halves away from zero
+    pub fn round(&self) -> Ratio<T> {
+        let (quotient, remainder) = self.floor_div();
+        let up = if self.numer.is_negative() { remainder > self.denom - remainder } else { remainder >= self.denom - remainder };
+        return Ratio::from_integer(if up { quotient + T::one() } else { quotient });
+    }
+
+    pub fn fract(&self) -> Ratio<T> {
+        return *self - self.trunc();
+    }
+
+    // ----------- FLOATS ------------
+
+    pub fn to_f64(&self) -> f64 {
+        return self.numer.to_i128() as f64 / self.denom.to_i128() as f64;
+    }
+
+    // The exact value of the float, every finite float is m·2^e. Fails for
+    // NaN, infinities and values that need more digits than T has.
+    pub fn from_f64(value: f64) -> Result<Ratio<T>, RatioError> {
+        if !value.is_finite() {
+            return Err(RatioError::OutOfRange);
+        }
+        if value == 0.0 {
+            return Ok(Ratio::zero());
+        }
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i32;
+        let mut mantissa = (bits & ((1 << 52) - 1)) as i128;
+        if exponent != 0 {
+            mantissa |= 1 << 52;
+        }
+        // value = mantissa · 2^shift, the shift is applied to the smaller part
+        let mut shift = if exponent == 0 { -1074 } else { exponent - 1075 };
+        let zeros = (mantissa.trailing_zeros() as i32).min(-shift.min(0));
+        mantissa >>= zeros;
+        shift += zeros;
+        if value < 0.0 {
+            mantissa = -mantissa;
+        }
+
+        let (numer, denom) = if shift >= 0 {
+            if shift > 126 - 53 {
+                return Err(RatioError::OutOfRange);
+            }
+            (mantissa << shift, 1)
+        } else {
+            if shift < -126 {
+                return Err(RatioError::OutOfRange);
+            }
+            (mantissa, 1i128 << -shift)
+        };
+        return match (T::from_i128(numer), T::from_i128(denom)) {
+            (Some(numer), Some(denom)) => Ok(Ratio { numer, denom }),
+            _ => Err(RatioError::OutOfRange)
+        };
+    }
+
+    // The closest fraction with a denominator of at most max_denom, from the
+    // convergents and semiconvergents of the continued fraction
+    pub fn approximate(value: f64, max_denom: T) -> Result<Ratio<T>, RatioError> {
+        if !value.is_finite() || max_denom < T::one() {
+            return Err(RatioError::OutOfRange);
+        }
+        let limit = max_denom.to_i128();
+        let (mut p0, mut q0, mut p1, mut q1) = (0i128, 1i128, 1i128, 0i128);
+        let mut rest = value;
+        loop {
+            let a = rest.floor();
+            if a.abs() > 1e30 {
+                return Err(RatioError::OutOfRange);
+            }
+            let a = a as i128;
+            let (p2, q2) = (a * p1 + p0, a * q1 + q0);
+            if q2 > limit {
+                // the best semiconvergent p0 + k·p1 / q0 + k·q1 below the limit
+                let k = (limit - q0) / q1;
+                let (ps, qs) = (p0 + k * p1, q0 + k * q1);
+                let semi = (ps as f64 / qs as f64 - value).abs();
+                let last = (p1 as f64 / q1 as f64 - value).abs();
+                if semi < last {
+                    (p1, q1) = (ps, qs);
+                }
+                break;
+            }
+            (p0, q0, p1, q1) = (p1, q1, p2, q2);
+            let fraction = rest - a as f64;
+            if fraction == 0.0 || (p1 as f64 / q1 as f64) == value {
+                break;
+            }
+            rest = 1.0 / fraction;
+        }
+        return match (T::from_i128(p1), T::from_i128(q1)) {
+            (Some(numer), Some(denom)) => Ok(Ratio::new(numer, denom)),
+            _ => Err(RatioError::OutOfRange)
+        };
+    }
+}
+
+impl<T: Integer + Signed> Default for Ratio<T> {
+    fn default() -> Ratio<T> {
+        return Ratio::zero();
+    }
+}
+
+impl<T: Integer + Signed> From<T> for Ratio<T> {
+    fn from(value: T) -> Ratio<T> {
+        return Ratio::from_integer(value);
+    }
+}
+
+impl<T: Integer + Signed> Zero for Ratio<T> {
+    fn zero() -> Ratio<T> {
+        return Ratio { numer: T::zero(), denom: T::one() };
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.numer.is_zero();
+    }
+}
+
+impl<T: Integer + Signed> One for Ratio<T> {
+    fn one() -> Ratio<T> {
+        return Ratio { numer: T::one(), denom: T::one() };
+    }
+}
+
+impl<T: Integer + Signed> Signed for Ratio<T> {
+    fn signum(self) -> Ratio<T> {
+        return Ratio::from_integer(self.numer.signum());
+    }
+}
+
+// ----------- ORDERING ------------
+
+// Compares the integer parts first and then the reciprocals of the
+// fractional parts, so nothing is multiplied and nothing overflows
+impl<T: Integer + Signed> Ord for Ratio<T> {
+    fn cmp(&self, other: &Ratio<T>) -> Ordering {
+        if self.denom == other.denom {
+            return self.numer.cmp(&other.numer);
+        }
+        let (self_int, self_rem) = self.floor_div();
+        let (other_int, other_rem) = other.floor_div();
+        return match self_int.cmp(&other_int) {
+            Ordering::Equal => match (self_rem.is_zero(), other_rem.is_zero()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => {
+                    let self_recip = Ratio { numer: self.denom, denom: self_rem };
+                    let other_recip = Ratio { numer: other.denom, denom: other_rem };
+                    other_recip.cmp(&self_recip)
+                }
+            },
+            ordering => ordering
+        };
+    }
+}
+
+impl<T: Integer + Signed> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Ratio<T>) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+// ----------- OPERATORS ------------
+
+// The gcd of the denominators is divided out first to keep the
+// intermediate values small
+impl<T: Integer + Signed> Add for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn add(self, other: Self) -> Self::Output {
+        let gcd = self.denom.gcd(other.denom);
+        let numer = self.numer * (other.denom / gcd) + other.numer * (self.denom / gcd);
+        return Ratio::new(numer, self.denom * (other.denom / gcd));
+    }
+}
+
+impl<T: Integer + Signed> Sub for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        return self + -other;
+    }
+}
+
+impl<T: Integer + Signed> Mul for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let first = self.numer.gcd(other.denom);
+        let second = other.numer.gcd(self.denom);
+        return Ratio::new((self.numer / first) * (other.numer / second),
+                          (self.denom / second) * (other.denom / first));
+    }
+}
+
+// Panics when dividing by zero
+impl<T: Integer + Signed> Div for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn div(self, other: Self) -> Self::Output {
+        if other.is_zero() {
+            panic!("ratio division by zero");
+        }
+        return self * other.recip();
+    }
+}
+
+// The remainder has the sign of self, like it does for the primitives
+impl<T: Integer + Signed> Rem for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn rem(self, other: Self) -> Self::Output {
+        return self - other * (self / other).trunc();
+    }
+}
+
+impl<T: Integer + Signed> Neg for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn neg(self) -> Self::Output {
+        return Ratio { numer: -self.numer, denom: self.denom };
+    }
+}
+
+macro_rules! impl_assign {
+    ($($assign:ident, $method:ident => $op:tt);*) => {$(
+        impl<T: Integer + Signed> $assign for Ratio<T> {
+            fn $method(&mut self, other: Self) {
+                *self = *self $op other;
+            }
+        }
+    )*};
+}
+
+impl_assign!(
+    AddAssign, add_assign => +;
+    SubAssign, sub_assign => -;
+    MulAssign, mul_assign => *;
+    DivAssign, div_assign => /;
+    RemAssign, rem_assign => %
+);
+
+impl<T: Integer + Signed> Sum for Ratio<T> {
+    fn sum<I: Iterator<Item = Ratio<T>>>(iter: I) -> Self {
+        return iter.fold(Ratio::zero(), |sum, value| sum + value);
+    }
+}
+
+impl<T: Integer + Signed> Product for Ratio<T> {
+    fn product<I: Iterator<Item = Ratio<T>>>(iter: I) -> Self {
+        return iter.fold(Ratio::one(), |product, value| product * value);
+    }
+}
+
+// Ratios divide exactly, the quotient of ratio parts is a ratio again
+impl<T: Integer + Signed> ComplexDiv for Ratio<T> {
+    fn checked_div(lhs: &Complex<Ratio<T>>, rhs: &Complex<Ratio<T>>) -> Result<Complex<Ratio<T>>, ComplexError> {
+        let square = rhs.re * rhs.re + rhs.im * rhs.im;
+        if square.is_zero() {
+            return Err(ComplexError::DivisionByZero);
+        }
+        return Ok(Complex {
+            re: (lhs.re * rhs.re + lhs.im * rhs.im) / square,
+            im: (lhs.im * rhs.re - lhs.re * rhs.im) / square
+        });
+    }
+}
+
+// ----------- PARSING AND FORMATTING ------------
+
+// "3/4", "-6/8" or "5", the parts are parsed by T
+impl<T: Integer + Signed + FromStr> FromStr for Ratio<T> {
+    type Err = RatioError;
+
+    fn from_str(text: &str) -> Result<Ratio<T>, RatioError> {
+        let parse = |part: &str| part.parse::<T>().map_err(|_| RatioError::InvalidNumber);
+        return match text.split_once('/') {
+            Some((numer, denom)) => Ratio::checked_new(parse(numer)?, parse(denom)?),
+            None => Ok(Ratio::from_integer(parse(text)?))
+        };
+    }
+}
+
+// Integers are written without the denominator
+impl<T: Integer + Signed + Display> Display for Ratio<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            return write!(f, "{}", self.numer);
+        }
+        return write!(f, "{}/{}", self.numer, self.denom);
+    }
+}
+
+#[test]
+fn test_ratio_arithmetic() {
+    let r = |numer: i64, denom: i64| Ratio::new(numer, denom);
+    assert_eq!(r(6, -8), r(-3, 4));
+    assert_eq!((r(6, -8).numer(), r(6, -8).denom()), (-3, 4));
+    assert_eq!(r(0, -5), Ratio::zero());
+    assert_eq!(Ratio::checked_new(1, 0), Err(RatioError::ZeroDenominator));
+    // the gcd or the negated parts of MIN do not fit the type
+    assert_eq!(Ratio::checked_new(i64::MIN, i64::MIN), Ok(Ratio::from_integer(1)));
+    assert_eq!(Ratio::checked_new(i64::MIN, -1), Err(RatioError::OutOfRange));
+    assert_eq!(Ratio::checked_new(1, i8::MIN), Err(RatioError::OutOfRange));
+    assert_eq!(Ratio::checked_new(2, i8::MIN), Ok(Ratio::new(-1, 64)));
+    assert_eq!(Ratio::checked_new(i128::MIN, 2), Ok(Ratio::from_integer(i128::MIN / 2)));
+
+    assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+    assert_eq!(r(1, 2) - r(1, 3), r(1, 6));
+    assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+    assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+    assert_eq!(r(7, 2) % r(1, 1), r(1, 2));
+    assert_eq!(r(-7, 2) % r(1, 1), r(-1, 2));
+    assert_eq!(-r(1, 2), r(-1, 2));
+    assert_eq!(r(2, 3).pow(3), r(8, 27));
+    assert_eq!(r(2, 3).pow(-2), r(9, 4));
+    assert_eq!(r(-3, 4).recip(), r(-4, 3));
+
+    let mut value = r(1, 2);
+    value += r(1, 4);
+    value -= r(1, 8);
+    value *= r(8, 3);
+    value /= r(5, 1);
+    assert_eq!(value, r(1, 3));
+    value %= r(1, 4);
+    assert_eq!(value, r(1, 12));
+
+    assert_eq!((1..=4).map(|n| r(1, n)).sum::<Ratio<i64>>(), r(25, 12));
+    assert_eq!((1..=4).map(|n| r(n, n + 1)).product::<Ratio<i64>>(), r(1, 5));
+
+    // reducing first keeps products of large ratios in range
+    let big = r(i64::MAX, 3);
+    assert_eq!(big * r(3, i64::MAX), Ratio::one());
+    assert_eq!(r(i64::MAX - 1, i64::MAX) + r(1, i64::MAX), Ratio::one());
+
+    assert_eq!(r(7, 2).floor(), r(3, 1));
+    assert_eq!(r(-7, 2).floor(), r(-4, 1));
+    assert_eq!(r(7, 2).ceil(), r(4, 1));
+    assert_eq!(r(-7, 2).ceil(), r(-3, 1));
+    assert_eq!(r(-7, 2).trunc(), r(-3, 1));
+    assert_eq!(r(7, 2).round(), r(4, 1));
+    assert_eq!(r(-7, 2).round(), r(-4, 1));
+    assert_eq!(r(-5, 3).round(), r(-2, 1));
+    assert_eq!(r(-7, 2).fract(), r(-1, 2));
+    assert!(r(4, 2).is_integer() && !r(1, 2).is_integer());
+    assert_eq!(Signed::abs(r(-1, 2)), r(1, 2));
+    assert_eq!(Signed::signum(r(-1, 2)), r(-1, 1));
+}
+
+#[test]
+#[should_panic(expected = "ratio division by zero")]
+fn test_ratio_division_by_zero() {
+    let _ = Ratio::new(1, 2) / Ratio::zero();
+}
+
+#[test]
+fn test_ratio_conversions() {
+    let r = |numer: i32, denom: i32| Ratio::new(numer, denom);
+    let mut values = vec![r(-7, 2), r(1, 3), r(-1, 3), r(0, 1), r(1, 2), r(2, 3), r(5, 1), r(i32::MAX, 1), r(1, i32::MAX)];
+    values.sort();
+    assert_eq!(values, vec![r(-7, 2), r(-1, 3), r(0, 1), r(1, i32::MAX), r(1, 3), r(1, 2), r(2, 3), r(5, 1), r(i32::MAX, 1)]);
+    assert!(r(i32::MAX - 2, i32::MAX - 1) < r(i32::MAX - 1, i32::MAX));
+    assert_eq!(Ratio::<i32>::default(), Ratio::zero());
+    assert_eq!(Ratio::from(5), r(5, 1));
+
+    assert_eq!("3/4".parse(), Ok(r(3, 4)));
+    assert_eq!("-6/8".parse(), Ok(r(-3, 4)));
+    assert_eq!("5".parse(), Ok(r(5, 1)));
+    assert_eq!("1/0".parse::<Ratio<i32>>(), Err(RatioError::ZeroDenominator));
+    assert_eq!("1/x".parse::<Ratio<i32>>(), Err(RatioError::InvalidNumber));
+    assert_eq!("".parse::<Ratio<i32>>(), Err(RatioError::InvalidNumber));
+    assert_eq!(format!("{}", r(-6, 8)), "-3/4");
+    assert_eq!(format!("{}", r(10, 5)), "2");
+    for value in [r(3, 4), r(-1, 7), r(0, 1), r(i32::MIN + 1, 3)] {
+        assert_eq!(format!("{}", value).parse(), Ok(value));
+    }
+
+    assert_eq!(r(3, 4).to_f64(), 0.75);
+    assert_eq!(Ratio::<i32>::from_f64(0.75), Ok(r(3, 4)));
+    assert_eq!(Ratio::<i32>::from_f64(-2.5), Ok(r(-5, 2)));
+    assert_eq!(Ratio::<i32>::from_f64(1024.0), Ok(r(1024, 1)));
+    assert_eq!(Ratio::<i64>::from_f64(0.1), Ok(Ratio::new(3602879701896397, 36028797018963968)));
+    assert_eq!(Ratio::<i32>::from_f64(0.1), Err(RatioError::OutOfRange));
+    assert_eq!(Ratio::<i32>::from_f64(f64::NAN), Err(RatioError::OutOfRange));
+    assert_eq!(Ratio::<i8>::from_f64(300.0), Err(RatioError::OutOfRange));
+
+    assert_eq!(Ratio::approximate(0.1, 1000), Ok(r(1, 10)));
+    assert_eq!(Ratio::approximate(std::f64::consts::PI, 10), Ok(r(22, 7)));
+    assert_eq!(Ratio::approximate(std::f64::consts::PI, 1000), Ok(r(355, 113)));
+    assert_eq!(Ratio::approximate(-0.3333, 100), Ok(r(-1, 3)));
+    assert_eq!(Ratio::approximate(2.0, 5), Ok(r(2, 1)));
+    assert_eq!(Ratio::approximate(0.5, 0), Err(RatioError::OutOfRange));
+}
@@ -0,0 +1,257 @@
+// Axis-aligned rectangles, shared by the task2 crates that need them:
+// mod num; mod approx; mod rectangle; and the prng module
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+use crate::prng::{Rng, SampleUniform};
+use crate::num::Num;
+use crate::approx::{ApproxEq, Difference};
+
+#[derive(Debug, PartialEq)]
+pub struct Rectangle<T: Sized> {
+    pub x: T,
+    pub y: T,
+    pub width: T,
+    pub height: T
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T
+}
+
+// std::cmp::min and max need Ord, these also take floats. The first argument
+// wins ties and NaNs, like min and max of Ord do for ties.
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    return if b < a { b } else { a };
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    return if b > a { b } else { a };
+}
+
+pub trait Rect<T: Default + Sized> : Default {
+    fn new(x: T, y : T, width: T, height: T) -> Self;
+    fn adjust(&mut self, dx1: T, dy1: T, dx2: T, dy2: T);
+    fn left(&self) -> T;
+    fn right(&self) -> T;
+    fn top(&self) -> T;
+    fn bottom(&self) -> T;
+    fn x(&self) -> T;
+    fn y(&self) -> T;
+    fn height(&self) -> T;
+    fn width(&self) -> T;
+    fn bottom_left(&self) -> Point<T>;
+    fn bottom_right(&self) -> Point<T>;
+    fn top_left(&self) -> Point<T>;
+    fn top_right(&self) -> Point<T>;
+    fn center(&self) -> Point<T>;
+    fn contains_point(&self, point: &Point<T>) -> bool;
+    fn contains_rect(&self, point: &Rectangle<T>) -> bool;
+    fn is_intersected(&self, rect: &Rectangle<T>) -> bool;
+    fn intersected(&self, rect: &Rectangle<T>) -> Rectangle<T>;
+    fn united(&self, rect: &Rectangle<T>) -> Rectangle<T>;
+    fn transposed(&self) -> Rectangle<T>;
+}
+
+impl<T: Default + Sized> Default for Rectangle<T> {
+    fn default() -> Self {
+        return Rectangle {
+            x: T::default(),
+            y: T::default(),
+            width: T::default(),
+            height: T::default()
+        };
+    }
+}
+
+impl<T: Num + Default + Debug + PartialOrd> Rect<T> for Rectangle<T> {
+    fn new(x: T, y: T, width: T, height: T) -> Self {
+        return Rectangle { x, y, width, height };
+    }
+
+    fn adjust(&mut self, dx1: T, dy1: T, dx2: T, dy2: T) {
+        self.x = self.x + dx1;
+        self.y = self.y + dy1;
+        self.width = self.width + dx2;
+        self.height = self.height + dy2;
+    }
+
+    fn left(&self) -> T {
+        return self.x;
+    }
+
+    fn right(&self) -> T {
+        return self.x + self.width;
+    }
+
+    fn top(&self) -> T {
+        return self.y + self.height;
+    }
+
+    fn bottom(&self) -> T {
+        return self.y;
+    }
+
+    fn x(&self) -> T {
+        return self.x;
+    }
+
+    fn y(&self) -> T {
+        return self.y;
+    }
+
+    fn height(&self) -> T {
+        return self.height;
+    }
+
+    fn width(&self) -> T {
+        return self.width;
+    }
+
+    fn bottom_left(&self) -> Point<T> {
+        return Point {
+            x: self.left(),
+            y: self.bottom()
+        };
+    }
+
+    fn bottom_right(&self) -> Point<T> {
+        return Point {
+            x: self.right(),
+            y: self.bottom()
+        };
+    }
+
+    fn top_left(&self) -> Point<T> {
+        return Point {
+            x: self.left(),
+            y: self.top()
+        };
+    }
+
+    fn top_right(&self) -> Point<T> {
+        return Point {
+            x: self.right(),
+            y: self.top()
+        };
+    }
+
+    fn center(&self) -> Point<T> {
+        return Point {
+            x: self.x + self.width / T::two(),
+            y: self.y + self.height / T::two()
+        };
+    }
+
+    fn contains_point(&self, point: &Point<T>) -> bool {
+        return self.x <= point.x && point.x <= self.x + self.width
+            && self.y <= point.y && point.y <= self.y + self.height;
+    }
+
+    fn contains_rect(&self, rect: &Rectangle<T>) -> bool {
+        let has_top_left: bool = self.contains_point(&rect.top_left());
+        let has_top_right: bool = self.contains_point(&rect.top_right());
+        let has_bottom_left: bool = self.contains_point(&rect.bottom_left());
+        let has_bottom_right: bool = self.contains_point(&rect.bottom_right());
+        return has_top_left && has_top_right && has_bottom_left && has_bottom_right;
+    }
+
+    fn is_intersected(&self, rect: &Rectangle<T>) -> bool {
+        // Is fully inside
+        if self.contains_rect(rect) || rect.contains_rect(self) {
+            return true;
+        }
+
+        let has_top_left: bool = self.contains_point(&rect.top_left());
+        let has_top_right: bool = self.contains_point(&rect.top_right());
+        let has_bottom_left: bool = self.contains_point(&rect.bottom_left());
+        let has_bottom_right: bool = self.contains_point(&rect.bottom_right());
+
+        // Is fully outside
+        if !has_top_left && !has_top_right && !has_bottom_right && !has_bottom_left {
+            return false;
+        }
+        return true;
+    }
+
+    fn intersected(&self, rect: &Rectangle<T>) -> Rectangle<T> {
+        if !self.is_intersected(rect) {
+            return Rectangle::default();
+        }
+
+        let top = min(self.top(), rect.top());
+        let left = max(self.left(), rect.left());
+        let right = min(self.right(), rect.right());
+        let bottom = max(self.bottom(), rect.bottom());
+        return Rectangle {
+            x: left,
+            y: bottom,
+            width: right - left,
+            height: top - bottom
+        };
+    }
+
+    fn united(&self, rect: &Rectangle<T>) -> Rectangle<T> {
+        let top = max(self.top(), rect.top());
+        let left = min(self.left(), rect.left());
+        let right = max(self.right(), rect.right());
+        let bottom = min(self.bottom(), rect.bottom());
+
+        return Rectangle {
+            x: left,
+            y: bottom,
+            width: right - left,
+            height: top - bottom
+        };
+    }
+
+    fn transposed(&self) -> Rectangle<T> {
+        return Rectangle {
+            x: self.x,
+            y: self.y,
+            width: self.height,
+            height: self.width
+        };
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Point<T> {
+    fn difference(&self, other: &Point<T>) -> Difference {
+        return self.x.difference(&other.x).max(self.y.difference(&other.y));
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Rectangle<T> {
+    fn difference(&self, other: &Rectangle<T>) -> Difference {
+        return self.x.difference(&other.x)
+            .max(self.y.difference(&other.y))
+            .max(self.width.difference(&other.width))
+            .max(self.height.difference(&other.height));
+    }
+}
+
+// ----------- RANDOM ------------
+
+impl<T: SampleUniform + Num> Point<T> {
+    pub fn random_within<R: Rng>(rng: &mut R, bounds: &Rectangle<T>) -> Point<T> {
+        return Point {
+            x: rng.range_inclusive(bounds.x..=bounds.x + bounds.width),
+            y: rng.range_inclusive(bounds.y..=bounds.y + bounds.height)
+        };
+    }
+}
+
+impl<T: SampleUniform + Num> Rectangle<T> {
+    // the whole rectangle, not only its origin, lies inside bounds
+    pub fn random_within<R: Rng>(rng: &mut R, bounds: &Rectangle<T>) -> Rectangle<T> {
+        let origin = Point::random_within(rng, bounds);
+        return Rectangle {
+            x: origin.x,
+            y: origin.y,
+            width: rng.range_inclusive(T::zero()..=bounds.x + bounds.width - origin.x),
+            height: rng.range_inclusive(T::zero()..=bounds.y + bounds.height - origin.y)
+        };
+    }
+}
@@ -1,3 +1,12 @@
+#[path = "../common/prng.rs"]
+mod prng;
+mod num;
+
+use std::iter::Sum;
+use std::ops::{Add, Range};
+use crate::prng::{Rng, SampleUniform};
+use crate::num::{Num, Zero};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Element<T, U> {
     re: T,
@@ -25,6 +34,47 @@ fn compare<T: PartialOrd, U: PartialOrd>(elem1: &Element<T, U>, elem2: &Element<
     return elem1.re > elem2.re;
 }
 
+impl<T: SampleUniform + Copy, U: SampleUniform + Copy> Element<T, U> {
+    fn random<R: Rng>(rng: &mut R, re: Range<T>, im: Range<U>) -> Element<T, U> {
+        return Element {
+            re: rng.range(re),
+            im: rng.range(im)
+        };
+    }
+
+    fn random_vec<R: Rng>(rng: &mut R, len: usize, re: Range<T>, im: Range<U>) -> Vec<Element<T, U>> {
+        return (0..len).map(|_| Element::random(rng, re.clone(), im.clone())).collect();
+    }
+}
+
+// Both parts are added on their own, so elements of any two Num types sum up
+impl<T: Num, U: Num> Add for Element<T, U> {
+    type Output = Element<T, U>;
+
+    fn add(self, other: Self) -> Self::Output {
+        return Element {
+            re: self.re + other.re,
+            im: self.im + other.im
+        };
+    }
+}
+
+impl<T: Num, U: Num> Zero for Element<T, U> {
+    fn zero() -> Element<T, U> {
+        return Element { re: T::zero(), im: U::zero() };
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.re.is_zero() && self.im.is_zero();
+    }
+}
+
+impl<T: Num, U: Num> Sum for Element<T, U> {
+    fn sum<I: Iterator<Item = Element<T, U>>>(iter: I) -> Self {
+        return iter.fold(Element::zero(), |sum, elem| sum + elem);
+    }
+}
+
 #[test]
 fn test_sort_array() {
     let comp_grt = |val1: &i32, val2: &i32| -> bool { val1 > val2 };
@@ -59,5 +109,40 @@ fn test_sort_elements() {
     assert_eq!(bubble_sort(&mut elems, compare), sorted_elems);
 }
 
+#[test]
+fn test_sum_elements() {
+    let elems = [Element {re: 1.5, im: 2u8}, Element {re: -0.5, im: 3}, Element {re: 2.0, im: 0}];
+    assert_eq!(elems.iter().copied().sum::<Element<f64, u8>>(), Element {re: 3.0, im: 5});
+    assert!(Vec::<Element<i32, i64>>::new().into_iter().sum::<Element<i32, i64>>().is_zero());
+}
+
+#[test]
+fn test_sort_random() {
+    use crate::prng::Xoshiro256;
+    let mut rng = Xoshiro256::new(35);
+    for len in [0, 1, 2, 10, 100] {
+        let mut arr: Vec<i32> = (0..len).map(|_| rng.range(-50..50)).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        assert_eq!(bubble_sort(&mut arr, |a, b| a > b), expected.as_slice());
+
+        // small ranges give plenty of equal re parts to order by im
+        let mut elems = Element::random_vec(&mut rng, len, 0..5, -3..3);
+        let mut expected = elems.clone();
+        expected.sort_by_key(|elem| (elem.re, elem.im));
+        assert_eq!(bubble_sort(&mut elems, compare), expected.as_slice());
+    }
+
+    let mut elems = Element::random_vec(&mut rng, 50, -1.0..1.0, 0.0..1.0f32);
+    bubble_sort(&mut elems, compare);
+    assert!(elems.windows(2).all(|pair| !compare(&pair[0], &pair[1])));
+
+    // shuffling sorted input and sorting again restores it
+    let sorted: Vec<u32> = (0..64).collect();
+    let mut shuffled = sorted.clone();
+    rng.shuffle(&mut shuffled);
+    assert_eq!(bubble_sort(&mut shuffled, |a, b| a > b), sorted.as_slice());
+}
+
 fn main() {
 }
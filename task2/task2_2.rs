@@ -1,123 +1,496 @@
-use std::fmt::Debug;
-use std::ops::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
+#[path = "../common/prng.rs"]
+mod prng;
+mod num;
+#[macro_use]
+mod approx;
+mod fft;
+mod matrix;
+mod polynomial;
+mod gaussian;
+mod quaternion;
+mod ratio;
+mod fixed;
+mod complex;
+mod dual;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-struct Complex<T> {
-    re: T,
-    im: T
+use crate::complex::{Complex, Scalar};
+
+#[test]
+fn test_operations() {
+    let val1: Complex<i32> = Complex {re: 0, im: 0};
+    let val2: Complex<i32> = Complex {re: 5, im: 1};
+
+    assert_eq!(val2 + val1, val2);
+    assert_eq!(val2 - val1, val2);
+    assert_eq!(val1 * val2, val1);
+    assert_eq!(val1 / val2, val1);
 }
 
-impl<T: Add<Output = T>> Add for Complex<T> {
-    type Output = Complex<T>;
+#[test]
+fn test_approx_operations() {
+    // float parts are rounded, compare them with a tolerance
+    let a: Complex<f64> = Complex {re: 0.1, im: 0.2};
+    let b: Complex<f64> = Complex {re: 0.3, im: -0.7};
+    assert_approx_eq!(a + b, Complex {re: 0.4, im: -0.5});
+    assert_approx_eq!((a - b) * Complex {re: 0.0, im: 1.0}, Complex {re: -0.9, im: -0.2});
+    assert_approx_eq!(a * b, Complex {re: 0.17, im: -0.01}, abs = 1e-15);
+    assert_approx_eq!(a * b / b, a, rel = 1e-15);
+    assert_approx_ne!(a, a.conj(), abs = 0.1);
+}
 
-    fn add(self, other: Self) -> Self::Output {
-        return Complex {
-            re: self.re + other.re,
-            im: self.im + other.im
-        };
-    }
+#[test]
+fn test_assign_operations() {
+    let mut val1: Complex<i32> = Complex {re: 1, im: 1};
+    val1 += Complex {re: 1, im: 1};
+    assert_eq!(val1, Complex {re: 2, im: 2});
+
+    val1 -= Complex {re: 1, im: 1};
+    assert_eq!(val1, Complex {re: 1, im: 1});
+
+    val1 *= Complex {re: 5, im: 10};
+    assert_eq!(val1, Complex {re: -5, im: 15});
+
+    val1 /= Complex {re: 2, im: -1};
+    assert_eq!(val1, Complex {re: -5, im: 5});
+}
+
+#[test]
+fn test_division() {
+    use crate::complex::ComplexError;
+    let val: Complex<i32> = Complex {re: 10, im: 5};
+    assert_eq!(val / Complex {re: 1, im: 2}, Complex {re: 4, im: -3});
+    assert_eq!(val / Complex {re: 0, im: 1}, Complex {re: 5, im: -10});
+    assert_eq!(val / Complex {re: -5, im: 0}, Complex {re: -2, im: -1});
+    assert_eq!(Complex {re: 3, im: 4} / Complex {re: 1, im: 2}, Complex {re: 2, im: 0}); // 2.2 - 0.4i
+    assert_eq!(Complex {re: 7u32, im: 0} / Complex {re: 2, im: 0}, Complex {re: 3, im: 0});
+
+    let val: Complex<f64> = Complex {re: 3.0, im: 4.0};
+    assert_eq!(val / Complex {re: 1.0, im: 2.0}, Complex {re: 2.2, im: -0.4});
+    assert_eq!(val / Complex {re: 0.0, im: 2.0}, Complex {re: 2.0, im: -1.5});
+    assert_eq!(val / val, Complex {re: 1.0, im: 0.0});
+    assert_eq!(Complex {re: 1.0f32, im: 1.0} / Complex {re: 1.0, im: -1.0}, Complex {re: 0.0, im: 1.0});
+
+    // the textbook formula overflows c² + d² here and gives NaN
+    let huge: Complex<f64> = Complex {re: 1e300, im: 1e300};
+    assert_eq!(huge / huge, Complex {re: 1.0, im: 0.0});
+    assert_eq!(huge / Complex {re: 1e300, im: -1e300}, Complex {re: 0.0, im: 1.0});
+    let tiny: Complex<f64> = Complex {re: 1e-300, im: 1e-300};
+    assert_eq!(tiny / tiny, Complex {re: 1.0, im: 0.0});
+
+    let mut val: Complex<f64> = Complex {re: 11.0, im: -2.0};
+    val /= Complex {re: 1.0, im: -2.0};
+    assert_eq!(val, Complex {re: 3.0, im: 4.0});
+
+    assert_eq!(Complex {re: 1, im: 1}.checked_div(&Complex {re: 0, im: 0}), Err(ComplexError::DivisionByZero));
+    assert_eq!(Complex {re: 1.0, im: 1.0}.checked_div(&Complex {re: -0.0, im: 0.0}), Err(ComplexError::DivisionByZero));
+    assert_eq!(Complex {re: 4, im: 2}.checked_div(&Complex {re: 2, im: 0}), Ok(Complex {re: 2, im: 1}));
+    assert_eq!(ComplexError::DivisionByZero.to_string(), "complex division by zero");
 }
 
-impl<T: Sub<Output = T>> Sub for Complex<T> {
-    type Output = Complex<T>;
+#[test]
+fn test_signed_division_overflow() {
+    use crate::complex::ComplexError;
+    // -128 / -1 = 128 does not fit an i8
+    assert_eq!(Complex {re: -128i8, im: 0}.checked_div(&Complex {re: -1, im: 0}), Err(ComplexError::Overflow));
+    assert_eq!(Complex {re: -128i16, im: 0}.checked_div(&Complex {re: -1, im: 0}), Ok(Complex {re: 128, im: 0}));
+    assert_eq!(Complex {re: i64::MIN, im: i64::MIN}.checked_div(&Complex {re: 1, im: 1}), Ok(Complex {re: i64::MIN, im: 0}));
+
+    // i128 components overflow the products themselves
+    assert_eq!(Complex {re: 1i128, im: 0}.checked_div(&Complex {re: i128::MAX, im: 1}), Err(ComplexError::Overflow));
+    assert_eq!(Complex {re: i128::MAX, im: 1}.checked_div(&Complex {re: 2, im: 2}), Err(ComplexError::Overflow));
+    assert_eq!(Complex {re: i128::MAX, im: 0}.checked_div(&Complex {re: 1, im: 0}), Ok(Complex {re: i128::MAX, im: 0}));
 
-    fn sub(self, other: Self) -> Self::Output {
-        return Complex {
-            re: self.re - other.re,
-            im: self.im - other.im
-        };
-    }
+    let result = std::panic::catch_unwind(|| Complex {re: -128i8, im: 0} / Complex {re: -1, im: 0});
+    assert_eq!(*result.unwrap_err().downcast::<String>().unwrap(), "complex division overflow");
 }
 
-impl<T: Mul<Output = T> +
-        Sub<Output = T> +
-        Add<Output = T> + Copy> Mul for Complex<T> {
-    type Output = Complex<T>;
+#[test]
+fn test_unsigned_division() {
+    use crate::complex::ComplexError;
+    // (1 + 0i) / (1 + i) = 0.5 - 0.5i, both parts truncate to 0
+    assert_eq!(Complex {re: 1u32, im: 0}.checked_div(&Complex {re: 1, im: 1}), Ok(Complex {re: 0, im: 0}));
+    assert_eq!(Complex {re: 1u32, im: 0} / Complex {re: 1, im: 1}, Complex {re: 0, im: 0});
+    // (0 + 4i) / (2 + 0i) = 0 + 2i and (4 + 0i) / (0 + 2i) = 0 - 2i
+    assert_eq!(Complex {re: 0u8, im: 4}.checked_div(&Complex {re: 2, im: 0}), Ok(Complex {re: 0, im: 2}));
+    assert_eq!(Complex {re: 4u8, im: 0}.checked_div(&Complex {re: 0, im: 2}), Err(ComplexError::Overflow));
 
-    fn mul(self, other: Self) -> Self::Output {
-        return Complex {
-            re: self.re * other.re - self.im * other.im,
-            im: self.re * other.im + self.im * other.re
-        };
-    }
+    // 16² + 16² does not fit a u8
+    assert_eq!(Complex {re: 1u8, im: 1}.checked_div(&Complex {re: 16, im: 16}), Err(ComplexError::Overflow));
+    assert_eq!(Complex {re: 200u8, im: 0}.checked_div(&Complex {re: 2, im: 0}), Err(ComplexError::Overflow));
+    assert_eq!(Complex {re: 200u16, im: 0}.checked_div(&Complex {re: 2, im: 0}), Ok(Complex {re: 100, im: 0}));
+    assert_eq!(Complex {re: u128::MAX, im: 0}.checked_div(&Complex {re: 1, im: 0}), Ok(Complex {re: u128::MAX, im: 0}));
+    assert_eq!(Complex {re: 0u64, im: 0}.checked_div(&Complex {re: 0, im: 0}), Err(ComplexError::DivisionByZero));
+    assert_eq!(ComplexError::Overflow.to_string(), "complex division overflow");
 }
 
-impl<T: Div<Output = T> +
-        Sub<Output = T> +
-        Mul<Output = T> +
-        Add<Output = T> + Copy> Div for Complex<T> {
-    type Output = Complex<T>;
+#[test]
+#[should_panic(expected = "complex division overflow")]
+fn test_unsigned_division_overflow() {
+    let _ = Complex {re: 1u8, im: 1} / Complex {re: 16, im: 16};
+}
 
-    fn div(self, other: Self) -> Self::Output {
-        let square = other.re * other.re + other.im + other.im; // TODO: check for zero
-        return Complex {
-            re: (self.re + other.re + self.im * other.im) / square,
-            im: (self.im * other.re - self.re * other.im) / square
-        }
-    }
+#[test]
+#[should_panic(expected = "complex division by zero")]
+fn test_division_by_zero() {
+    let _ = Complex {re: 1, im: 2} / Complex {re: 0, im: 0};
 }
 
-impl<T: AddAssign<T>> AddAssign for Complex<T> {
-    fn add_assign(&mut self, other: Self) {
-        self.re += other.re;
-        self.im += other.im;
-    }
+#[test]
+fn test_gaussian_integers() {
+    use crate::complex::ComplexError;
+    let c = |re: i64, im: i64| Complex {re, im};
+    assert_eq!(c(27, 23).div_rem(&c(8, 1)), Ok((c(4, 2), c(-3, 3))));
+    assert_eq!(c(1, 1).div_rem(&c(0, 0)), Err(ComplexError::DivisionByZero));
+    assert_eq!(c(5, 0).gcd(&c(3, 4)), c(2, 1));
+    assert_eq!(c(-3, -2).normalize(), c(3, 2));
+    assert!(c(0, -1).is_unit() && c(2, 1).divides(&c(5, 0)));
+    assert_eq!(c(i64::MIN, i64::MIN).norm_sqr(), 1 << 127);
+
+    assert!(c(2, 1).is_gaussian_prime() && !c(5, 0).is_gaussian_prime());
+    // large norms go through Miller-Rabin instead of trial division
+    assert!(c((1 << 61) - 1, 0).is_gaussian_prime());
+    assert!(c(0, -((1 << 61) - 1)).is_gaussian_prime());
+    assert!(c(4611686018427387905, 4611686018427388048).is_gaussian_prime());
+    assert!(!c(4611686018427387905, 4611686018427387905).is_gaussian_prime());
+    // a strong pseudoprime to the bases up to 23, of the form 4k + 3
+    assert!(!c(3825123056546413051, 0).is_gaussian_prime());
+
+    let (unit, factors) = c(3, 4).factorize().unwrap();
+    assert_eq!((unit, factors), (c(1, 0), vec![(c(2, 1), 2)]));
+}
+
+#[test]
+fn test_scalar_operations() {
+    let val: Complex<i32> = Complex {re: 3, im: -4};
+    assert_eq!(val + 2, Complex {re: 5, im: -4});
+    assert_eq!(val - 2, Complex {re: 1, im: -4});
+    assert_eq!(val * 2, Complex {re: 6, im: -8});
+    assert_eq!(val / 2, Complex {re: 1, im: -2});
+    assert_eq!(2 + val, Complex {re: 5, im: -4});
+    assert_eq!(2 - val, Complex {re: -1, im: 4});
+    assert_eq!(2 * val, Complex {re: 6, im: -8});
+    assert_eq!(50 / val, Complex {re: 6, im: 8});
+    assert_eq!(-val, Complex {re: -3, im: 4});
+    assert_eq!(-&val, Complex {re: -3, im: 4});
+
+    let mut val: Complex<f64> = Complex {re: 1.0, im: 2.0};
+    val += 1.0;
+    assert_eq!(val, Complex {re: 2.0, im: 2.0});
+    val -= 0.5;
+    assert_eq!(val, Complex {re: 1.5, im: 2.0});
+    val *= 2.0;
+    assert_eq!(val, Complex {re: 3.0, im: 4.0});
+    val /= 4.0;
+    assert_eq!(val, Complex {re: 0.75, im: 1.0});
+    assert_eq!(1.0 / Complex {re: 0.0, im: 2.0}, Complex {re: 0.0, im: -0.5});
+    assert_eq!(Complex {re: 1.0, im: 1.0} / 0.0, Complex {re: f64::INFINITY, im: f64::INFINITY});
+
+    let a: Complex<u8> = Complex {re: 2, im: 3};
+    assert_eq!(a * 2u8, 2u8 * a);
 }
 
-impl<T: SubAssign<T> + Copy> SubAssign for Complex<T> {
-    fn sub_assign(&mut self, other: Self) {
-        self.re -= other.re;
-        self.im -= other.im;
+#[test]
+fn test_reference_operations() {
+    use std::ops::{Add, Sub, Mul, Neg};
+
+    // a component type without Copy, the reference operators must not need clones
+    #[derive(Debug, Clone, PartialEq)]
+    struct Owned(Box<i64>);
+
+    impl Add for Owned {
+        type Output = Owned;
+        fn add(self, other: Owned) -> Owned { return Owned(Box::new(*self.0 + *other.0)); }
     }
+    impl Sub for Owned {
+        type Output = Owned;
+        fn sub(self, other: Owned) -> Owned { return Owned(Box::new(*self.0 - *other.0)); }
+    }
+    impl<'a, 'b> Add<&'b Owned> for &'a Owned {
+        type Output = Owned;
+        fn add(self, other: &'b Owned) -> Owned { return Owned(Box::new(*self.0 + *other.0)); }
+    }
+    impl<'a, 'b> Sub<&'b Owned> for &'a Owned {
+        type Output = Owned;
+        fn sub(self, other: &'b Owned) -> Owned { return Owned(Box::new(*self.0 - *other.0)); }
+    }
+    impl<'a, 'b> Mul<&'b Owned> for &'a Owned {
+        type Output = Owned;
+        fn mul(self, other: &'b Owned) -> Owned { return Owned(Box::new(*self.0 * *other.0)); }
+    }
+    impl<'a> Neg for &'a Owned {
+        type Output = Owned;
+        fn neg(self) -> Owned { return Owned(Box::new(-*self.0)); }
+    }
+
+    let owned = |re: i64, im: i64| Complex {re: Owned(Box::new(re)), im: Owned(Box::new(im))};
+    let a = owned(1, 2);
+    let b = owned(3, -4);
+    assert_eq!(&a + &b, owned(4, -2));
+    assert_eq!(&a - &b, owned(-2, 6));
+    assert_eq!(&a * &b, owned(11, 2));
+    assert_eq!(&a * &Owned(Box::new(3)), owned(3, 6));
+    assert_eq!(-&a, owned(-1, -2));
+
+    let a: Complex<i32> = Complex {re: 10, im: 5};
+    let b: Complex<i32> = Complex {re: 1, im: 2};
+    assert_eq!(&a + &b, a + b);
+    assert_eq!(&a - &b, a - b);
+    assert_eq!(&a * &b, a * b);
+    assert_eq!(&a / &b, a / b);
+    assert_eq!(&a * &3, a * 3);
+}
+
+#[test]
+fn test_sum_product() {
+    let values: Vec<Complex<i32>> = vec![
+        Complex {re: 1, im: 1},
+        Complex {re: 2, im: -1},
+        Complex {re: 0, im: 3}
+    ];
+    assert_eq!(values.iter().sum::<Complex<i32>>(), Complex {re: 3, im: 3});
+    assert_eq!(values.clone().into_iter().sum::<Complex<i32>>(), Complex {re: 3, im: 3});
+    assert_eq!(values.iter().product::<Complex<i32>>(), Complex {re: -3, im: 9});
+    assert_eq!(values.into_iter().product::<Complex<i32>>(), Complex {re: -3, im: 9});
+
+    let empty: Vec<Complex<f64>> = vec![];
+    assert_eq!(empty.iter().sum::<Complex<f64>>(), Complex {re: 0.0, im: 0.0});
+    assert_eq!(empty.iter().product::<Complex<f64>>(), Complex {re: 1.0, im: 0.0});
+
+    // the fourth powers of i multiply to one
+    let i: Complex<f64> = Complex {re: 0.0, im: 1.0};
+    assert_eq!(std::iter::repeat(i).take(8).product::<Complex<f64>>(), Complex {re: 1.0, im: 0.0});
+}
+
+#[test]
+fn test_parse() {
+    use crate::complex::{ParseComplexError, ParseComplexErrorKind};
+    let parse = |text: &str| text.parse::<Complex<f64>>();
+    assert_eq!(parse("3+4i"), Ok(Complex {re: 3.0, im: 4.0}));
+    assert_eq!(parse("-2.5-1e3j"), Ok(Complex {re: -2.5, im: -1000.0}));
+    assert_eq!(parse("1e-3-2E+2i"), Ok(Complex {re: 0.001, im: -200.0}));
+    assert_eq!(parse("5i"), Ok(Complex {re: 0.0, im: 5.0}));
+    assert_eq!(parse("-i"), Ok(Complex {re: 0.0, im: -1.0}));
+    assert_eq!(parse("2+j"), Ok(Complex {re: 2.0, im: 1.0}));
+    assert_eq!(parse("7"), Ok(Complex {re: 7.0, im: 0.0}));
+    assert_eq!(parse("-7e2"), Ok(Complex {re: -700.0, im: 0.0}));
+    assert_eq!(parse("(1,2)"), Ok(Complex {re: 1.0, im: 2.0}));
+    assert_eq!(parse(" ( -1.5 , 2e1 ) "), Ok(Complex {re: -1.5, im: 20.0}));
+    assert_eq!(parse("  3 - 4i "), Ok(Complex {re: 3.0, im: -4.0}));
+    assert_eq!(parse("inf-infi"), Ok(Complex {re: f64::INFINITY, im: f64::NEG_INFINITY}));
+    assert_eq!("-3+4i".parse::<Complex<i32>>(), Ok(Complex {re: -3, im: 4}));
+    assert_eq!("(10,20)".parse::<Complex<u8>>(), Ok(Complex {re: 10, im: 20}));
+
+    let error = |kind, position| Err(ParseComplexError { kind, position });
+    assert_eq!(parse(""), error(ParseComplexErrorKind::Empty, 0));
+    assert_eq!(parse("   "), error(ParseComplexErrorKind::Empty, 3));
+    assert_eq!(parse("3+4"), error(ParseComplexErrorKind::MissingUnit, 3));
+    assert_eq!(parse("3x+4i"), error(ParseComplexErrorKind::InvalidNumber, 0));
+    assert_eq!(parse("3+4xi"), error(ParseComplexErrorKind::InvalidNumber, 1));
+    assert_eq!(parse(" 1.2.3"), error(ParseComplexErrorKind::InvalidNumber, 1));
+    assert_eq!(parse("(1,2"), error(ParseComplexErrorKind::UnclosedParenthesis, 4));
+    assert_eq!(parse("(1,2)x"), error(ParseComplexErrorKind::UnexpectedCharacter, 5));
+    assert_eq!(parse("(1 2)"), error(ParseComplexErrorKind::MissingComma, 4));
+    assert_eq!(parse("(1, b)"), error(ParseComplexErrorKind::InvalidNumber, 4));
+    assert_eq!("1.5+2i".parse::<Complex<i32>>().unwrap_err().position, 0);
+    assert_eq!("3-4i".parse::<Complex<u8>>().unwrap_err().kind, ParseComplexErrorKind::InvalidNumber);
+
+    let message = parse("3+4").unwrap_err().to_string();
+    assert_eq!(message, "missing imaginary unit at 3");
 }
 
-impl<T: Mul<Output = T> +
-        Sub<Output = T> +
-        Add<Output = T> + Copy> MulAssign for Complex<T> {
-    fn mul_assign(&mut self, other: Self) {
-        let (re, im) = (self.re, self.im);
-        self.re = re * other.re - im * other.im;
-        self.im = re * other.im + im * other.re;
+#[test]
+fn test_format() {
+    use crate::prng::{Rng, Pcg32};
+    let val: Complex<f64> = Complex {re: 1.0, im: -2.5};
+    assert_eq!(format!("{}", val), "1-2.5i");
+    assert_eq!(format!("{:.3}", val), "1.000-2.500i");
+    assert_eq!(format!("{:+.1}", val), "+1.0-2.5i");
+    assert_eq!(format!("{}", val.with_unit('j')), "1-2.5j");
+    assert_eq!(format!("{:.2}", val.with_unit('j')), "1.00-2.50j");
+    assert_eq!(format!("{:e}", Complex {re: 1500.0, im: 0.25}), "1.5e3+2.5e-1i");
+    assert_eq!(format!("{:.2E}", Complex {re: 1500.0, im: -0.25}), "1.50E3-2.50E-1i");
+    assert_eq!(format!("{:>10}", Complex {re: 3, im: 4}), "      3+4i");
+    assert_eq!(format!("{:*<8}", Complex {re: 3, im: -4}), "3-4i****");
+    assert_eq!(format!("{:^8}", Complex {re: 3, im: 4}.with_unit('j')), "  3+4j  ");
+    assert_eq!(format!("{:+}", Complex {re: -3, im: 0}), "-3+0i");
+    assert_eq!(format!("{:+}", Complex {re: 3u8, im: 0}), "+3+0i");
+
+    // Display and both exponent forms parse back to the same value
+    let mut rng = Pcg32::new(39, 0);
+    for _ in 0..1000 {
+        let val: Complex<f64> = Complex::random(&mut rng, -1e6..1e6) / rng.range(1e-6..1e6);
+        assert_eq!(format!("{}", val).parse::<Complex<f64>>(), Ok(val));
+        assert_eq!(format!("{:e}", val).parse::<Complex<f64>>(), Ok(val));
+        assert_eq!(format!("{:E}", val.with_unit('j')).parse::<Complex<f64>>(), Ok(val));
+        assert_eq!(format!("({}, {})", val.re, val.im).parse::<Complex<f64>>(), Ok(val));
+
+        let val: Complex<i64> = Complex::random(&mut rng, -1000..1000);
+        assert_eq!(format!("{}", val).parse::<Complex<i64>>(), Ok(val));
     }
 }
 
-impl<T: Div<Output = T> +
-        Mul<Output = T> +
-        Sub<Output = T> +
-        Add<Output = T> + Copy + Debug> DivAssign for Complex<T> {
-    fn div_assign(&mut self, other: Self) {
-        let (re, im) = (self.re, self.im);
-        let square = other.re * other.re + other.im * other.im; // TODO: check for zero
-        self.re = (re * other.re + im * other.im) / square;
-        self.im = (im * other.re - re * other.im) / square;
+#[test]
+fn test_exact_components() {
+    use crate::num::Zero;
+    use crate::ratio::Ratio;
+    use crate::complex::ComplexError;
+    let r = |numer: i64, denom: i64| Ratio::new(numer, denom);
+    let a = Complex { re: r(1, 2), im: r(-1, 3) };
+    let b = Complex { re: r(2, 1), im: r(3, 4) };
+    assert_eq!(a * b, Complex { re: r(5, 4), im: r(-7, 24) });
+    assert_eq!(a / b * b, a);
+    assert_eq!(a / b, Complex { re: r(12, 73), im: r(-50, 219) });
+    assert_eq!(a.checked_div(&Complex::zero()), Err(ComplexError::DivisionByZero));
+    assert_eq!(format!("{}", a), "1/2-1/3i");
+
+    // the gaussian quotient rounds while the ratio one is exact
+    let (c, d) = (Complex { re: 7, im: 3 }, Complex { re: 2, im: -1 });
+    let exact = Complex { re: r(c.re, 1), im: r(c.im, 1) } / Complex { re: r(d.re, 1), im: r(d.im, 1) };
+    assert_eq!(exact, Complex { re: r(11, 5), im: r(13, 5) });
+    assert_eq!(c / d, Complex { re: 2, im: 3 });
+
+    let mut product = vec![a, b, a].into_iter().product::<Complex<Ratio<i64>>>();
+    product /= a;
+    assert_eq!(product, a * b);
+}
+
+#[test]
+fn test_fixed_components() {
+    use crate::num::Zero;
+    use crate::fixed::Fixed;
+    use crate::complex::ComplexError;
+    type Q16 = Fixed<i32, 16>;
+    let q = |re: f64, im: f64| Complex { re: Q16::from_f64(re).unwrap(), im: Q16::from_f64(im).unwrap() };
+    let (a, b) = (q(1.5, -2.0), q(0.5, 0.25));
+    assert_eq!(a + b, q(2.0, -1.75));
+    assert_eq!(a * b, q(1.25, -0.625));
+    assert_eq!(a * b / b, a);
+    assert_eq!(q(1.0, 0.0) / q(0.0, 2.0), q(0.0, -0.5));
+    assert_eq!(a.checked_div(&Complex::zero()), Err(ComplexError::DivisionByZero));
+    assert_eq!(format!("{}", a), "1.5-2i");
+    assert_eq!("1.5-2i".parse(), Ok(a));
+
+    // the same bits on every machine, within a few ulps of the float iteration
+    let c = q(-0.75, 0.125);
+    let mut z: Complex<Q16> = Complex::zero();
+    for _ in 0..20 {
+        z = z * z + c;
     }
+    assert_eq!((z.re.to_bits(), z.im.to_bits()), (-43854, -23988));
+}
+
+fn close(a: Complex<f64>, b: Complex<f64>) -> bool {
+    return (a - b).norm() <= 1e-12 * (1.0 + b.norm());
 }
 
 #[test]
-fn test_operations() {
-    let val1: Complex<i32> = Complex {re: 0, im: 0};
-    let val2: Complex<i32> = Complex {re: 5, im: 1};
+fn test_functions() {
+    use std::f64::consts::{E, PI, FRAC_PI_2};
+    use crate::prng::Pcg32;
+    let i: Complex<f64> = Complex {re: 0.0, im: 1.0};
+    let one: Complex<f64> = Complex {re: 1.0, im: 0.0};
+    let z: Complex<f64> = Complex {re: 3.0, im: -4.0};
 
-    assert_eq!(val2 + val1, val2);
-    assert_eq!(val2 - val1, val2);
-    assert_eq!(val1 * val2, val1);
-    assert_eq!(val1 / val2, val1);
+    assert_eq!(z.norm(), 5.0);
+    assert_eq!(z.norm_sqr(), 25.0);
+    assert_eq!(z.conj(), Complex {re: 3.0, im: 4.0});
+    assert_eq!(Complex {re: 2, im: -7}.conj(), Complex {re: 2, im: 7});
+    assert_eq!(i.arg(), FRAC_PI_2);
+    assert_eq!(Complex {re: -1.0f64, im: 0.0}.arg(), PI);
+    assert_eq!(Complex {re: -1.0f64, im: -0.0}.arg(), -PI);
+    assert!(close(Complex::<f64>::from_polar(2.0, FRAC_PI_2), Complex {re: 0.0, im: 2.0}));
+    let (r, theta) = z.to_polar();
+    assert!(close(Complex::<f64>::from_polar(r, theta), z));
+
+    assert!(close(Complex {re: 0.0f64, im: PI}.exp(), Complex {re: -1.0, im: 0.0}));
+    assert!(close(one.exp(), Complex {re: E, im: 0.0}));
+    assert!(close(Complex {re: -1.0f64, im: 0.0}.ln(), Complex {re: 0.0, im: PI}));
+    assert!(close(Complex {re: -1.0f64, im: -0.0}.ln(), Complex {re: 0.0, im: -PI}));
+    assert!(close(z.ln().exp(), z));
+    assert!(close(z.exp().ln(), Complex {re: 3.0, im: -4.0 + 2.0 * PI}));
+
+    assert_eq!(Complex {re: -4.0f64, im: 0.0}.sqrt(), Complex {re: 0.0, im: 2.0});
+    assert_eq!(Complex {re: -4.0f64, im: -0.0}.sqrt(), Complex {re: 0.0, im: -2.0});
+    assert_eq!(Complex {re: 0.0f64, im: 0.0}.sqrt(), Complex {re: 0.0, im: 0.0});
+    assert!(close(Complex {re: 0.0f64, im: 2.0}.sqrt(), Complex {re: 1.0, im: 1.0}));
+    assert!(close(i.powc(i), Complex {re: (-FRAC_PI_2).exp(), im: 0.0}));
+    assert!(close(z.powf(2.0), z * z));
+    assert!(close(z.powf(0.5), z.sqrt()));
+    assert!(close(z.powc(Complex {re: 3.0, im: 0.0}), z * z * z));
+    assert_eq!(Complex {re: 0.0f64, im: 0.0}.powc(Complex {re: 0.0, im: 0.0}), one);
+    assert_eq!(Complex {re: 0.0f64, im: 0.0}.powc(one), Complex {re: 0.0, im: 0.0});
+
+    assert!(close(Complex {re: FRAC_PI_2, im: 0.0}.sin(), one));
+    assert!(close(Complex {re: 0.0f64, im: 1.0}.cos(), Complex {re: 1.0f64.cosh(), im: 0.0}));
+    assert_eq!(Complex {re: 1.0f64, im: 400.0}.tan(), i);
+    assert_eq!(Complex {re: -400.0f64, im: 1.0}.tanh(), Complex {re: -1.0, im: 0.0});
+
+    let mut rng = Pcg32::new(37, 0);
+    for _ in 0..1000 {
+        let z: Complex<f64> = Complex::random(&mut rng, -3.0..3.0);
+        let (sin, cos) = (z.sin(), z.cos());
+        let (sinh, cosh) = (z.sinh(), z.cosh());
+        assert!(close(sin * sin + cos * cos, one));
+        assert!(close(cosh * cosh - sinh * sinh, one));
+        assert!(close(z.tan(), sin / cos));
+        assert!(close(z.tanh(), sinh / cosh));
+        assert!(close((i * z).sin(), i * sinh));
+        assert!(close((i * z).cos(), cosh));
+        assert!(close(z.sqrt() * z.sqrt(), z));
+        assert!(z.sqrt().re >= 0.0);
+        assert!(close(z.ln().exp(), z));
+        assert!(close((z + z.conj()).exp(), z.exp() * z.conj().exp()));
+        assert!((z.exp().norm() - z.re.exp()).abs() < 1e-12 * z.re.exp());
+    }
+
+    let z: Complex<f32> = Complex {re: 1.0, im: 1.0};
+    assert!((z.sqrt() * z.sqrt() - z).norm() < 1e-6);
+    assert!((z.ln().exp() - z).norm() < 1e-6);
+    let (r, theta) = z.to_polar();
+    assert!((Complex::<f32>::from_polar(r, theta) - z).norm_sqr() < 1e-12);
+    assert!((z.powf(3.0) - z.powc(Complex {re: 3.0, im: 0.0})).norm() < 1e-5);
+    assert!((z.tan() - z.sin() / z.cos()).norm() < 1e-6);
+    assert!((z.tanh() - z.sinh() / z.cosh()).norm() < 1e-6);
+    assert_eq!(z.arg(), std::f32::consts::FRAC_PI_4);
 }
 
 #[test]
-fn test_assign_operations() {
-    let mut val1: Complex<i32> = Complex {re: 1, im: 1};
-    val1 += Complex {re: 1, im: 1};
-    assert_eq!(val1, Complex {re: 2, im: 2});
+fn test_random_operations() {
+    use crate::prng::Pcg32;
+    use crate::complex::ComplexError;
+    let mut rng = Pcg32::new(35, 2);
+    let zero = Complex {re: 0, im: 0};
+    for _ in 0..1000 {
+        let a: Complex<i64> = Complex::random(&mut rng, -1000..1000);
+        let b: Complex<i64> = Complex::random(&mut rng, -1000..1000);
+        let c: Complex<i64> = Complex::random(&mut rng, -1000..1000);
 
-    val1 -= Complex {re: 1, im: 1};
-    assert_eq!(val1, Complex {re: 1, im: 1});
+        assert_eq!(a + b - b, a);
+        assert_eq!(a + b, b + a);
+        assert_eq!(a * b, b * a);
+        assert_eq!((a * b) * c, a * (b * c));
+        assert_eq!(a * (b + c), a * b + a * c);
 
-    val1 *= Complex {re: 5, im: 10};
-    assert_eq!(val1, Complex {re: -5, im: 15});
+        // a product of gaussian integers divides exactly
+        if b != zero {
+            assert_eq!((a * b) / b, a);
+            let mut product = a * b;
+            product /= b;
+            assert_eq!(product, a);
+        } else {
+            assert_eq!(a.checked_div(&b), Err(ComplexError::DivisionByZero));
+        }
+    }
 
-    val1 /= Complex {re: 2, im: -1};
-    assert_eq!(val1, Complex {re: -5, im: 5});
+    for _ in 0..1000 {
+        let a: Complex<f64> = Complex::random(&mut rng, -10.0..10.0);
+        let b: Complex<f64> = Complex::random(&mut rng, -10.0..10.0);
+        assert!(a.re >= -10.0 && a.re < 10.0 && a.im >= -10.0 && a.im < 10.0);
+
+        let quotient = a / b;
+        let back = quotient * b;
+        assert!((back.re - a.re).abs() < 1e-9 && (back.im - a.im).abs() < 1e-9);
+    }
 }
 
 fn main() {
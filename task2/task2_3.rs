@@ -1,212 +1,20 @@
 // task 3
-use std::fmt::Debug;
-use std::cmp::{min, max};
-use std::ops::{Add, Div, Sub};
-
-#[derive(Debug, PartialEq)]
-struct Rectangle<T: Sized> {
-    x: T,
-    y: T,
-    width: T,
-    height: T
-}
-
-#[derive(Debug, PartialEq)]
-struct Point<T> {
-    x: T,
-    y: T
-}
-
-trait Rect<T: Default + Sized> : Default {
-    fn new(x: T, y : T, width: T, height: T) -> Self;
-    fn adjust(&mut self, dx1: T, dy1: T, dx2: T, dy2: T);
-    fn left(&self) -> T;
-    fn right(&self) -> T;
-    fn top(&self) -> T;
-    fn bottom(&self) -> T;
-    fn x(&self) -> T;
-    fn y(&self) -> T;
-    fn height(&self) -> T;
-    fn width(&self) -> T;
-    fn bottom_left(&self) -> Point<T>;
-    fn bottom_right(&self) -> Point<T>;
-    fn top_left(&self) -> Point<T>;
-    fn top_right(&self) -> Point<T>;
-    fn center(&self) -> Point<T>;
-    fn contains_point(&self, point: &Point<T>) -> bool;
-    fn contains_rect(&self, point: &Rectangle<T>) -> bool;
-    fn is_intersected(&self, rect: &Rectangle<T>) -> bool;
-    fn intersected(&self, rect: &Rectangle<T>) -> Rectangle<T>;
-    fn united(&self, rect: &Rectangle<T>) -> Rectangle<T>;
-    fn transposed(&self) -> Rectangle<T>;
-}
-
-impl<T: Default + Sized> Default for Rectangle<T> {
-    fn default() -> Self {
-        return Rectangle {
-            x: T::default(),
-            y: T::default(),
-            width: T::default(),
-            height: T::default()
-        };
-    }
-}
-
-impl<T: Default + Debug + Copy + Ord> Rect<T> for Rectangle<T>
-    where T: Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<i32>
-{
-    fn new(x: T, y: T, width: T, height: T) -> Self {
-        return Rectangle { x, y, width, height };
-    }
-
-    fn adjust(&mut self, dx1: T, dy1: T, dx2: T, dy2: T) {
-        self.x = self.x + dx1;
-        self.y = self.y + dy1;
-        self.width = self.width + dx2;
-        self.height = self.height + dy2;
-    }
-
-    fn left(&self) -> T {
-        return self.x;
-    }
-
-    fn right(&self) -> T {
-        return self.x + self.width;
-    }
-
-    fn top(&self) -> T {
-        return self.y + self.height;
-    }
-
-    fn bottom(&self) -> T {
-        return self.y;
-    }
-
-    fn x(&self) -> T {
-        return self.x;
-    }
-
-    fn y(&self) -> T {
-        return self.y;
-    }
-
-    fn height(&self) -> T {
-        return self.height;
-    }
-
-    fn width(&self) -> T {
-        return self.width;
-    }
-
-    fn bottom_left(&self) -> Point<T> {
-        return Point {
-            x: self.left(),
-            y: self.bottom()
-        };
-    }
-
-    fn bottom_right(&self) -> Point<T> {
-        return Point {
-            x: self.right(),
-            y: self.bottom()
-        };
-    }
-
-    fn top_left(&self) -> Point<T> {
-        return Point {
-            x: self.left(),
-            y: self.top()
-        };
-    }
-
-    fn top_right(&self) -> Point<T> {
-        return Point {
-            x: self.right(),
-            y: self.top()
-        };
-    }
-
-    fn center(&self) -> Point<T> {
-        return Point {
-            x: self.x + self.width / T::from(2),
-            y: self.y + self.height / T::from(2)
-        };
-    }
-
-    fn contains_point(&self, point: &Point<T>) -> bool {
-        return self.x <= point.x && point.x <= self.x + self.width
-            && self.y <= point.y && point.y <= self.y + self.height;
-    }
-
-    fn contains_rect(&self, rect: &Rectangle<T>) -> bool {
-        let has_top_left: bool = self.contains_point(&rect.top_left());
-        let has_top_right: bool = self.contains_point(&rect.top_right());
-        let has_bottom_left: bool = self.contains_point(&rect.bottom_left());
-        let has_bottom_right: bool = self.contains_point(&rect.bottom_right());
-        return has_top_left && has_top_right && has_bottom_left && has_bottom_right;
-    }
-
-    fn is_intersected(&self, rect: &Rectangle<T>) -> bool {
-        // Is fully inside
-        if self.contains_rect(rect) || rect.contains_rect(self) {
-            return true;
-        }
-
-        let has_top_left: bool = self.contains_point(&rect.top_left());
-        let has_top_right: bool = self.contains_point(&rect.top_right());
-        let has_bottom_left: bool = self.contains_point(&rect.bottom_left());
-        let has_bottom_right: bool = self.contains_point(&rect.bottom_right());
-
-        // Is fully outside
-        if !has_top_left && !has_top_right && !has_bottom_right && !has_bottom_left {
-            return false;
-        }
-        return true;
-    }
-
-    fn intersected(&self, rect: &Rectangle<T>) -> Rectangle<T> {
-        if !self.is_intersected(rect) {
-            return Rectangle::default();
-        }
-
-        let top = min(self.top(), rect.top());
-        let left = max(self.left(), rect.left());
-        let right = min(self.right(), rect.right());
-        let bottom = max(self.bottom(), rect.bottom());
-        return Rectangle {
-            x: left,
-            y: bottom,
-            width: right - left,
-            height: top - bottom
-        };
-    }
-
-    fn united(&self, rect: &Rectangle<T>) -> Rectangle<T> {
-        let top = max(self.top(), rect.top());
-        let left = min(self.left(), rect.left());
-        let right = max(self.right(), rect.right());
-        let bottom = min(self.bottom(), rect.bottom());
-
-        return Rectangle {
-            x: left,
-            y: bottom,
-            width: right - left,
-            height: top - bottom
-        };
-    }
-
-    fn transposed(&self) -> Rectangle<T> {
-        return Rectangle {
-            x: self.x,
-            y: self.y,
-            width: self.height,
-            height: self.width
-        };
-    }
-}
+mod morton;
+mod num;
+#[macro_use]
+mod approx;
+mod ratio;
+mod fixed;
+#[path = "../common/prng.rs"]
+mod prng;
+mod complex;
+mod rectangle;
+
+use crate::rectangle::Rectangle;
 
 #[test]
 fn test_parameters() {
+    use crate::rectangle::Rect;
     let rect: Rectangle<i32> = Rectangle {x: 10, y: 20, width: 50, height: 45};
 
     assert_eq!(rect.x(), 10);
@@ -217,6 +25,7 @@ fn test_parameters() {
 
 #[test]
 fn test_borders() {
+    use crate::rectangle::{Point, Rect};
     let rect: Rectangle<i32> = Rectangle {x: 10, y: 20, width: 50, height: 45};
 
     assert_eq!(rect.left(), 10);
@@ -232,6 +41,7 @@ fn test_borders() {
 
 #[test]
 fn test_adjust() {
+    use crate::rectangle::Rect;
     let mut rect: Rectangle<i32> = Rectangle {x: 10, y: 20, width: 50, height: 45};
 
     assert_eq!(Rectangle::new(10, 20, 50, 45), rect);
@@ -243,6 +53,7 @@ fn test_adjust() {
 
 #[test]
 fn test_intersections() {
+    use crate::rectangle::{Point, Rect};
     let rect: Rectangle<i32> = Rectangle {x: 0, y: 10, width: 60, height: 50};
 
     assert_eq!(rect.contains_point(&Point {x: 10, y: 20}), true);
@@ -277,6 +88,7 @@ fn test_intersections() {
 
 #[test]
 fn test_operations() {
+    use crate::rectangle::Rect;
     let rect: Rectangle<i32> = Rectangle {x: 10, y: 20, width: 50, height: 45};
 
     assert_eq!(rect.transposed(), Rectangle {x: 10, y: 20, width: 45, height: 50});
@@ -306,6 +118,7 @@ fn test_operations() {
 
 #[test]
 fn test_center() {
+    use crate::rectangle::{Point, Rect};
     assert_eq!(Rectangle::new(0, 0, 50, 40).center(),
                Point{ x: 25, y: 20 });
     assert_eq!(Rectangle::new(0, 100, 200, 100).center(),
@@ -316,6 +129,91 @@ fn test_center() {
                Point{ x: 0, y: 0 });
 }
 
+#[test]
+fn test_center_widths() {
+    use crate::rectangle::{Point, Rect};
+    // any Num works, not only types that convert from i32
+    assert_eq!(Rectangle::<u8>::new(10, 20, 200, 35).center(),
+               Point{ x: 110, y: 37 });
+    assert_eq!(Rectangle::<i64>::new(-1 << 40, 0, 1 << 41, 6).center(),
+               Point{ x: 0, y: 3 });
+}
+
+#[test]
+fn test_float_rectangle() {
+    use crate::rectangle::{Point, Rect};
+    // floats are only PartialOrd
+    let rect = Rectangle::new(-2.5, -1.25, 3.5, 2.5);
+    assert_eq!(rect.center(), Point{ x: -0.75, y: 0.0 });
+    assert!(rect.contains_point(&Point{ x: 1.0, y: 1.25 }));
+    assert_eq!(rect.intersected(&Rectangle::new(-1.0, 0.5, 4.0, 4.0)),
+               Rectangle::new(-1.0, 0.5, 2.0, 0.75));
+}
+
+#[test]
+fn test_approx_rectangle() {
+    use crate::rectangle::{Point, Rect};
+    assert_approx_eq!(Rectangle::new(0.1, 0.2, 0.3, 0.3).center(), Point{ x: 0.25, y: 0.35 });
+    assert_approx_eq!(Rectangle::new(0.1, 0.2, 0.3, 0.3).united(&Rectangle::new(0.7, 0.2, 0.1, 0.1)),
+                      Rectangle::new(0.1, 0.2, 0.7, 0.3), abs = 1e-15);
+}
+
+#[test]
+fn test_ratio_rectangle() {
+    use crate::ratio::Ratio;
+    use crate::rectangle::{Point, Rect};
+    // ratios keep the half that integer division drops
+    let r = |numer: i64, denom: i64| Ratio::new(numer, denom);
+    let rect = Rectangle::new(r(0, 1), r(1, 3), r(5, 1), r(1, 2));
+    assert_eq!(rect.center(), Point{ x: r(5, 2), y: r(7, 12) });
+    assert!(rect.contains_point(&Point{ x: r(5, 1), y: r(5, 6) }));
+    assert!(!rect.contains_point(&Point{ x: r(5, 1), y: r(6, 7) }));
+    assert_eq!(rect.intersected(&Rectangle::new(r(1, 2), r(1, 2), r(10, 1), r(1, 1))),
+               Rectangle::new(r(1, 2), r(1, 2), r(9, 2), r(1, 3)));
+}
+
+#[test]
+fn test_fixed_rectangle() {
+    use crate::fixed::Fixed;
+    use crate::rectangle::{Point, Rect};
+    let q = |text: &str| text.parse::<Fixed<i32, 8>>().unwrap();
+    let rect = Rectangle::new(q("-1.5"), q("0.25"), q("3"), q("0.75"));
+    assert_eq!(rect.center(), Point{ x: q("0"), y: q("0.625") });
+    assert_eq!(rect.united(&Rectangle::new(q("1"), q("2"), q("0.5"), q("0.5"))),
+               Rectangle::new(q("-1.5"), q("0.25"), q("3"), q("2.25")));
+}
+
+#[test]
+fn test_random_rectangles() {
+    use crate::prng::Xoshiro256;
+    use crate::rectangle::{Point, Rect};
+    let mut rng = Xoshiro256::new(35);
+    let bounds: Rectangle<i32> = Rectangle::new(-100, -50, 200, 100);
+    for _ in 0..1000 {
+        let a = Rectangle::random_within(&mut rng, &bounds);
+        let b = Rectangle::random_within(&mut rng, &bounds);
+        let point = Point::random_within(&mut rng, &a);
+
+        assert!(bounds.contains_rect(&a) && bounds.contains_rect(&b));
+        assert!(a.width() >= 0 && a.height() >= 0);
+        assert!(a.contains_point(&point));
+        assert_eq!(a.transposed().transposed(), a);
+
+        let united = a.united(&b);
+        assert!(united.contains_rect(&a) && united.contains_rect(&b));
+        assert!(bounds.contains_rect(&united));
+        assert_eq!(united, b.united(&a));
+
+        if a.is_intersected(&b) {
+            let intersected = a.intersected(&b);
+            assert!(a.contains_rect(&intersected) && b.contains_rect(&intersected));
+        }
+    }
+
+    let empty: Rectangle<i32> = Rectangle::new(5, 5, 0, 0);
+    assert_eq!(Rectangle::random_within(&mut rng, &empty), empty);
+}
+
 fn main() {
     let rect: Rectangle<i32> = Rectangle {x: 10, y: 15, width: 5, height: 8};
     println!("{:?}", rect);
@@ -1,5 +1,7 @@
 use std::str::FromStr;
+use std::ops::Range;
 use std::fmt::{Debug, Display, Formatter};
+use crate::prng::{Rng, SampleUniform};
 
 pub struct TreeNode<T> {
     pub value: T,
@@ -33,6 +35,17 @@ impl<T: PartialOrd + Copy + Debug> BinaryTree<T> {
         }
     }
 
+    // len values drawn from range, added in the order they are drawn
+    pub fn random<R: Rng>(rng: &mut R, len: usize, range: Range<T>) -> BinaryTree<T>
+        where T: SampleUniform
+    {
+        let mut tree = BinaryTree::Empty;
+        for _ in 0..len {
+            tree.add(rng.range(range.start..range.end));
+        }
+        return tree;
+    }
+
     fn create_leaf(value: T) -> BinaryTree<T> {
         let node = TreeNode {
             value,
@@ -1,4 +1,6 @@
 pub mod binary_tree;
+#[path = "../common/prng.rs"]
+mod prng;
 
 use std::fmt::Debug;
 use std::str::FromStr;
@@ -230,6 +232,37 @@ fn test5() {
                vec![0, 1]);
 }
 
+#[test]
+fn test_random() {
+    use crate::prng::{Rng, Xoshiro256};
+    let mut rng = Xoshiro256::new(35);
+    for len in [0, 1, 10, 500] {
+        let tree = BinaryTree::random(&mut rng, len, -100..100);
+        let values = tree_to_vec(&tree);
+        assert_eq!(values.len(), len);
+        assert!(values.iter().all(|x| (-100..100).contains(x)));
+
+        // adding the values in pre-order rebuilds the same tree
+        let rebuilt = BinaryTreeIterator::from_iter(values.clone()).collect::<Vec<i32>>();
+        assert_eq!(rebuilt, values);
+        assert_eq!(tree_to_vec(&tree.clone()), values);
+
+        // the same values in another order give the same multiset
+        let mut shuffled = values.clone();
+        rng.shuffle(&mut shuffled);
+        let mut from_shuffled = BinaryTreeIterator::from_iter(shuffled).collect::<Vec<i32>>();
+        let mut sorted = values.clone();
+        from_shuffled.sort();
+        sorted.sort();
+        assert_eq!(from_shuffled, sorted);
+    }
+
+    // the same seed reproduces the same tree
+    let first = BinaryTree::random(&mut Xoshiro256::new(7), 100, 0..1000);
+    let second = BinaryTree::random(&mut Xoshiro256::new(7), 100, 0..1000);
+    assert_eq!(tree_to_vec(&first), tree_to_vec(&second));
+}
+
 fn main() {
     // create and fill tree
     let mut tree = BinaryTree::Empty;